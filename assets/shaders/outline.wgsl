@@ -0,0 +1,39 @@
+#import bevy_pbr::{
+    mesh_functions,
+    forward_io::{Vertex, VertexOutput},
+    view_transformations::position_world_to_clip,
+}
+#import bevy_render::instance_index::get_instance_index
+
+struct OutlineMaterial {
+    color: vec4<f32>,
+    width: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: OutlineMaterial;
+
+// Pushes the vertices out along their normals - with front faces culled, only the
+// back of this enlarged hull is visible around the edges of the original mesh.
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    let model = mesh_functions::get_model_matrix(vertex.instance_index);
+    let world_normal = mesh_functions::mesh_normal_local_to_world(
+        vertex.normal,
+        get_instance_index(vertex.instance_index)
+    );
+    let world_position = mesh_functions::mesh_position_local_to_world(model, vec4<f32>(vertex.position, 1.0));
+
+    out.world_normal = world_normal;
+    out.world_position = vec4<f32>(world_position.xyz + normalize(world_normal) * material.width, 1.0);
+    out.position = position_world_to_clip(out.world_position.xyz);
+
+    return out;
+}
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    return material.color;
+}
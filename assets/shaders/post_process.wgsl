@@ -0,0 +1,71 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_render::globals::Globals
+
+struct PostProcessSettings {
+    vignette_intensity: f32,
+    vignette_radius: f32,
+    vignette_softness: f32,
+    chromatic_aberration: f32,
+    grain: f32,
+    _padding_0: f32,
+    _padding_1: f32,
+    _padding_2: f32,
+}
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+@group(0) @binding(2) var<uniform> settings: PostProcessSettings;
+@group(0) @binding(3) var palette_texture: texture_2d<f32>;
+@group(0) @binding(4) var<uniform> globals: Globals;
+
+// Replaces the colour with the closest one in the palette - a single row of pixels.
+fn quantize(color: vec3<f32>) -> vec3<f32> {
+    let size = textureDimensions(palette_texture).x;
+    var closest = color;
+    var closest_distance = 1e9;
+    for (var i = 0u; i < size; i++) {
+        let candidate = textureLoad(palette_texture, vec2<u32>(i, 0u), 0).rgb;
+        let offset = candidate - color;
+        let distance = dot(offset, offset);
+        if distance < closest_distance {
+            closest_distance = distance;
+            closest = candidate;
+        }
+    }
+    return closest;
+}
+
+fn hash(p: vec2<f32>) -> f32 {
+    let q = fract(p * vec2<f32>(123.34, 456.21));
+    let r = q + dot(q, q + 45.32);
+    return fract(r.x * r.y);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let centered = in.uv - vec2<f32>(0.5);
+    let shift = centered * settings.chromatic_aberration;
+
+    let base = textureSample(screen_texture, texture_sampler, in.uv);
+    let red = textureSample(screen_texture, texture_sampler, in.uv + shift).r;
+    let blue = textureSample(screen_texture, texture_sampler, in.uv - shift).b;
+    var color = vec3<f32>(red, base.g, blue);
+
+    let distance = length(centered) * 1.41421356;
+    let darkening = smoothstep(
+        settings.vignette_radius - settings.vignette_softness,
+        settings.vignette_radius,
+        distance
+    );
+    color *= 1.0 - darkening * settings.vignette_intensity;
+
+    let noise = hash(in.position.xy + fract(globals.time) * 100.0) - 0.5;
+    color += vec3<f32>(noise * settings.grain);
+
+    // A 1x1 palette is the fallback image bound when palette quantisation is off.
+    if textureDimensions(palette_texture).x > 1u {
+        color = quantize(color);
+    }
+
+    return vec4<f32>(color, base.a);
+}
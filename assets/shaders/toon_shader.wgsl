@@ -1,31 +1,47 @@
-#import bevy_pbr::mesh_vertex_output MeshVertexOutput
-#import bevy_pbr::mesh_view_bindings    view
-#import bevy_pbr::pbr_types             STANDARD_MATERIAL_FLAGS_DOUBLE_SIDED_BIT
-#import bevy_core_pipeline::tonemapping tone_mapping
-#import bevy_pbr::pbr_functions as fns
+#import bevy_pbr::{
+    forward_io::VertexOutput,
+    mesh_view_bindings::{view, lights},
+    pbr_types::{STANDARD_MATERIAL_FLAGS_DOUBLE_SIDED_BIT, PbrInput, pbr_input_new},
+    pbr_functions as fns,
+}
+
+struct ToonMaterial {
+    base_color: vec4<f32>,
+    band_count: f32,
+    rim_strength: f32,
+    specular_step: f32,
+    flags: u32,
+};
+
+const TOON_MATERIAL_FLAGS_COLOR_TEXTURE: u32 = 1u;
+const TOON_MATERIAL_FLAGS_SHADOW_TEXTURE: u32 = 2u;
 
 @group(1) @binding(0)
-var base_color_texture: texture_2d<f32>;
+var<uniform> material: ToonMaterial;
 @group(1) @binding(1)
-var base_color_sampler: sampler;
+var base_color_texture: texture_2d<f32>;
 @group(1) @binding(2)
-var shadow_color_texture: texture_2d<f32>;
+var base_color_sampler: sampler;
 @group(1) @binding(3)
+var shadow_color_texture: texture_2d<f32>;
+@group(1) @binding(4)
 var shadow_color_sampler: sampler;
 
 @fragment
 fn fragment(
     @builtin(front_facing) is_front: bool,
-    mesh: MeshVertexOutput,
+    mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    let layer = i32(mesh.world_position.x) & 0x3;
+    var color = material.base_color;
+#ifdef VERTEX_UVS
+    if (material.flags & TOON_MATERIAL_FLAGS_COLOR_TEXTURE) != 0u {
+        color = color * textureSample(base_color_texture, base_color_sampler, mesh.uv);
+    }
+#endif
 
-    let color =  textureSample(base_color_texture, base_color_sampler, mesh.uv);
+    var pbr_input: PbrInput = pbr_input_new();
 
-    var pbr_input: fns::PbrInput = fns::pbr_input_new();
-    
-    pbr_input.material.base_color =color;
-    // pbr_input.material.base_color = vec4<f32>(1., 1., 1., 1.);
+    pbr_input.material.base_color = color;
 
     pbr_input.frag_coord = mesh.position;
     pbr_input.world_position = mesh.world_position;
@@ -37,37 +53,45 @@ fn fragment(
 
     pbr_input.is_orthographic = view.projection[3].w == 1.0;
 
-    pbr_input.N = fns::apply_normal_mapping(
-        pbr_input.material.flags,
-        mesh.world_normal,
-#ifdef VERTEX_TANGENTS
-#ifdef STANDARDMATERIAL_NORMAL_MAP
-        mesh.world_tangent,
-#endif
-#endif
-        mesh.uv,
-        view.mip_bias,
-    );
+    pbr_input.N = normalize(pbr_input.world_normal);
     pbr_input.V = fns::calculate_view(mesh.world_position, pbr_input.is_orthographic);
 
+    let result = fns::apply_pbr_lighting(pbr_input);
 
-    let result = fns::pbr(pbr_input);
+    let min_channel = min(result.x, min(result.y, result.z));
+    let max_channel = max(result.x, max(result.y, result.z));
 
-    let min = min(result.x, min(result.y, result.z));
-    let max = max(result.x, max(result.y, result.z));
+    var l = clamp((max_channel + min_channel) / 2.0, 0.0, 1.0);
+    if material.band_count >= 1.0 {
+        l = ceil(l * material.band_count) / material.band_count;
+    }
 
-    let max_m_min = max - min;
-    let max_p_min = max + min;
+    var shadow = vec4<f32>(l, l, l, 1.0);
+    if (material.flags & TOON_MATERIAL_FLAGS_SHADOW_TEXTURE) != 0u {
+        shadow = textureSample(shadow_color_texture, shadow_color_sampler, vec2<f32>(l, 0.5));
+    }
 
-    let l = max_p_min / 2.0;
+    let result_shadeless = clamp(result / max(l, 0.0001), vec4<f32>(0., 0., 0., 0.), vec4<f32>(1., 1., 1., 1.));
 
-    let shadow = textureSample(shadow_color_texture, shadow_color_sampler, vec2<f32>(l, 0.5));
+    let shaded = color * shadow;
+    let shaded_with_hue = result_shadeless * shaded;
 
-    let result_shadeless = clamp(result / l, vec4<f32>(0., 0., 0., 0.), vec4<f32>(1., 1., 1., 1.));
+    var output = 1. - (1. - shaded) * (1. - shaded_with_hue);
 
-    let shaded = color * shadow;
-    let shaded_with_hue = result_shadeless * shaded;   
+    // Rim lighting along the silhouette
+    let rim = 1.0 - max(dot(pbr_input.N, pbr_input.V), 0.0);
+    output = output + vec4<f32>(vec3<f32>(step(0.7, rim) * material.rim_strength * l), 0.0);
+
+    // A hard edged specular highlight from the main directional light
+    if material.specular_step < 1.0 && lights.n_directional_lights > 0u {
+        let light = lights.directional_lights[0];
+        let half_vector = normalize(light.direction_to_light + pbr_input.V);
+        let specular = pow(max(dot(pbr_input.N, half_vector), 0.0), 32.0);
+        let light_color = light.color.rgb / max(max(light.color.r, light.color.g), max(light.color.b, 0.0001));
+        output = output + vec4<f32>(step(material.specular_step, specular) * light_color, 0.0);
+    }
+
+    output.a = color.a;
 
-    return 1. - (1. - shaded) * (1. - shaded_with_hue);
-    // return result;
+    return fns::main_pass_post_lighting_processing(pbr_input, output);
 }
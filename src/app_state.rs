@@ -7,6 +7,9 @@ pub enum AppState {
     #[default]
     LoadingMenu,
     MainMenu,
+    LevelSelect,
     Credits,
+    SaveSlots,
+    Settings,
     InGame,
 }
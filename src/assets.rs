@@ -1,8 +1,13 @@
-use bevy::prelude::*;
+use std::path::Path;
+
+use bevy::{prelude::*, utils::HashMap};
 use bevy_asset_loader::prelude::{AssetCollection, LoadingState, LoadingStateAppExt};
 use bevy_inspector_egui::{prelude::ReflectInspectorOptions, InspectorOptions};
 
-use crate::{app_state::AppState, menus::credits::Credits};
+use crate::{
+    app_state::AppState, levels::LevelManifest, lighting::LightingProfile, menus::credits::Credits,
+    particles::ParticleEffect, post_process::PostProcessProfile, prefabs::Prefab,
+};
 
 pub struct MainGameAssetPlugin;
 
@@ -11,7 +16,11 @@ impl Plugin for MainGameAssetPlugin {
         app.add_loading_state(
             LoadingState::new(AppState::LoadingMenu).continue_to_state(AppState::MainMenu),
         )
-        .add_collection_to_loading_state::<_, MainGameAssets>(AppState::LoadingMenu);
+        .add_collection_to_loading_state::<_, MainGameAssets>(AppState::LoadingMenu)
+        .add_collection_to_loading_state::<_, PrefabAssets>(AppState::LoadingMenu);
+
+        #[cfg(feature = "2d")]
+        app.add_collection_to_loading_state::<_, SpriteAssets>(AppState::LoadingMenu);
     }
 }
 
@@ -25,4 +34,56 @@ pub struct MainGameAssets {
 
     #[asset(path = "credits.cr.yaml")]
     pub credits: Handle<Credits>,
+
+    #[asset(path = "ramps/default.ramp.yaml")]
+    pub default_ramp: Handle<Image>,
+
+    #[asset(path = "lighting/menu.light.yaml")]
+    pub menu_lighting: Handle<LightingProfile>,
+    #[asset(path = "lighting/in_game.light.yaml")]
+    pub in_game_lighting: Handle<LightingProfile>,
+
+    #[asset(path = "particles/hit.particles.yaml")]
+    pub hit_particles: Handle<ParticleEffect>,
+    #[asset(path = "particles/pickup.particles.yaml")]
+    pub pickup_particles: Handle<ParticleEffect>,
+    #[asset(path = "particles/dust.particles.yaml")]
+    pub dust_particles: Handle<ParticleEffect>,
+
+    #[asset(path = "levels/campaign.levels.yaml")]
+    pub level_manifest: Handle<LevelManifest>,
+
+    #[asset(path = "post_process/default.post.yaml")]
+    pub post_process: Handle<PostProcessProfile>,
+    #[asset(path = "textures/palette.png")]
+    pub palette: Handle<Image>,
+}
+
+/// The prefabs that can be spawned by name - add new `.prefab.yaml` files to the list.
+#[derive(AssetCollection, Resource)]
+pub struct PrefabAssets {
+    #[asset(
+        paths("prefabs/lantern.prefab.yaml", "prefabs/keeper.prefab.yaml"),
+        collection(typed, mapped)
+    )]
+    pub prefabs: HashMap<String, Handle<Prefab>>,
+}
+
+impl PrefabAssets {
+    /// Finds a prefab by its file name, without the `.prefab.yaml` extension.
+    pub fn get(&self, name: &str) -> Option<&Handle<Prefab>> {
+        self.prefabs.iter().find_map(|(path, handle)| {
+            let file_name = Path::new(path).file_name()?.to_str()?;
+            (file_name.strip_suffix(".prefab.yaml")? == name).then_some(handle)
+        })
+    }
+}
+
+/// Sprite sheets for the 2D mode, split into texture atlases as they load.
+#[cfg(feature = "2d")]
+#[derive(AssetCollection, Resource)]
+pub struct SpriteAssets {
+    #[asset(texture_atlas(tile_size_x = 16., tile_size_y = 16., columns = 4, rows = 1))]
+    #[asset(path = "textures/characters.png")]
+    pub characters: Handle<TextureAtlas>,
 }
@@ -0,0 +1,91 @@
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        view::RenderLayers,
+    },
+};
+
+use super::{CameraPresets, MainCamera, PixelPerfect};
+
+/// The render layer holding the upscaled image - nothing else should be placed on it.
+const LOW_RES_LAYER: u8 = 31;
+
+/// The image the main camera renders to when a low resolution is set in the [`CameraPresets`].
+#[derive(Resource, Clone, Debug)]
+#[allow(dead_code)]
+pub struct LowResTarget(pub Handle<Image>);
+
+/// The camera drawing the low resolution image to the window.
+#[derive(Component)]
+struct LowResCamera;
+
+/// Points the main camera at a fixed size image, and draws it to the window
+/// scaled up by a whole number, with black bars filling the rest.
+pub(super) fn setup_low_res(
+    mut commands: Commands,
+    presets: Res<CameraPresets>,
+    mut images: ResMut<Assets<Image>>,
+    mut cameras: Query<(Entity, &mut Camera), With<MainCamera>>,
+) {
+    let Some(resolution) = presets.low_resolution else {
+        return;
+    };
+    let size = Extent3d {
+        width: resolution.x,
+        height: resolution.y,
+        depth_or_array_layers: 1,
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("low_res_target"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    image.resize(size);
+    let image = images.add(image);
+
+    for (entity, mut camera) in cameras.iter_mut() {
+        camera.target = RenderTarget::Image(image.clone());
+        camera.order = -1;
+        commands
+            .entity(entity)
+            .insert(UiCameraConfig { show_ui: false })
+            .remove::<PixelPerfect>();
+    }
+
+    let layer = RenderLayers::layer(LOW_RES_LAYER);
+    commands.spawn((
+        SpriteBundle {
+            texture: image.clone(),
+            ..default()
+        },
+        layer,
+    ));
+    commands.spawn((
+        Camera2dBundle {
+            camera_2d: Camera2d {
+                clear_color: ClearColorConfig::Custom(Color::BLACK),
+            },
+            ..default()
+        },
+        UiCameraConfig { show_ui: false },
+        PixelPerfect { resolution },
+        LowResCamera,
+        layer,
+    ));
+    commands.insert_resource(LowResTarget(image));
+}
@@ -0,0 +1,169 @@
+use bevy::{
+    core_pipeline::{clear_color::ClearColorConfig, tonemapping::Tonemapping},
+    prelude::*,
+    render::view::VisibilitySystems,
+    transform::TransformSystem,
+};
+
+use crate::app_state::AppState;
+
+mod low_res;
+mod pixel;
+mod rig;
+mod shake;
+
+pub use pixel::PixelPerfect;
+pub use rig::CameraRig;
+pub use shake::CameraShake;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraPresets>()
+            .configure_sets(
+                PostUpdate,
+                CameraSystems
+                    .after(TransformSystem::TransformPropagate)
+                    .before(VisibilitySystems::UpdateOrthographicFrusta)
+                    .before(VisibilitySystems::UpdatePerspectiveFrusta)
+                    .before(VisibilitySystems::UpdateProjectionFrusta),
+            )
+            .add_plugins((
+                rig::CameraRigPlugin,
+                shake::CameraShakePlugin,
+                pixel::PixelCameraPlugin,
+            ))
+            .add_systems(
+                Startup,
+                (setup, apply_deferred, low_res::setup_low_res).chain(),
+            )
+            .add_systems(OnEnter(AppState::InGame), enter_game)
+            .add_systems(OnExit(AppState::InGame), reset_camera);
+    }
+}
+
+/// Systems positioning the camera, after transforms are propagated but before visibility is checked.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CameraSystems;
+
+/// The main camera, rendering the game world.
+#[derive(Component)]
+pub struct MainCamera;
+
+/// The camera rendering the UI on top of the world.
+#[derive(Component)]
+pub struct UiCamera;
+
+/// How the main camera is set up in each [`AppState`].
+#[derive(Resource, Clone, Debug)]
+pub struct CameraPresets {
+    /// The fixed position of the camera outside of the game
+    pub menu: Transform,
+    /// The rig used while in game
+    pub in_game: CameraRig,
+    /// The shake added alongside the rig while in game
+    pub shake: CameraShake,
+    /// When set, the world is rendered at this resolution and scaled up to fit the window,
+    /// while the UI stays at the window's resolution
+    pub low_resolution: Option<UVec2>,
+}
+
+#[cfg(not(feature = "2d"))]
+impl Default for CameraPresets {
+    fn default() -> Self {
+        Self {
+            menu: Transform::from_translation(Vec3::new(-2., 5., -5.)).looking_at(Vec3::Y, Vec3::Y),
+            in_game: CameraRig::default(),
+            shake: CameraShake::default(),
+            low_resolution: None,
+        }
+    }
+}
+
+/// The 2D camera sits at the far end of its depth range, looking down the Z axis.
+#[cfg(feature = "2d")]
+const CAMERA_2D_DEPTH: f32 = 999.9;
+
+#[cfg(feature = "2d")]
+impl Default for CameraPresets {
+    fn default() -> Self {
+        Self {
+            menu: Transform::from_xyz(0., 0., CAMERA_2D_DEPTH),
+            in_game: CameraRig::side_scroller(CAMERA_2D_DEPTH, 0.),
+            shake: CameraShake {
+                max_offset: 6.,
+                max_roll: 0.,
+                ..default()
+            },
+            low_resolution: None,
+        }
+    }
+}
+
+fn setup(mut commands: Commands, presets: Res<CameraPresets>) {
+    spawn_main_camera(&mut commands, &presets);
+
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: 1,
+                ..default()
+            },
+            camera_2d: Camera2d {
+                clear_color: ClearColorConfig::None,
+            },
+            tonemapping: Tonemapping::AcesFitted,
+            ..default()
+        },
+        UiCamera,
+    ));
+}
+
+#[cfg(not(feature = "2d"))]
+fn spawn_main_camera(commands: &mut Commands, presets: &CameraPresets) {
+    commands.spawn((
+        Camera3dBundle {
+            transform: presets.menu,
+            tonemapping: Tonemapping::AcesFitted,
+            ..default()
+        },
+        MainCamera,
+    ));
+}
+
+#[cfg(feature = "2d")]
+fn spawn_main_camera(commands: &mut Commands, presets: &CameraPresets) {
+    commands.spawn((
+        Camera2dBundle {
+            transform: presets.menu,
+            tonemapping: Tonemapping::AcesFitted,
+            ..default()
+        },
+        MainCamera,
+        PixelPerfect::default(),
+    ));
+}
+
+fn enter_game(
+    mut commands: Commands,
+    presets: Res<CameraPresets>,
+    cameras: Query<Entity, With<MainCamera>>,
+) {
+    for camera in cameras.iter() {
+        commands
+            .entity(camera)
+            .insert((presets.in_game.clone(), presets.shake.clone()));
+    }
+}
+
+fn reset_camera(
+    mut commands: Commands,
+    presets: Res<CameraPresets>,
+    mut cameras: Query<(Entity, &mut Transform), With<MainCamera>>,
+) {
+    for (camera, mut transform) in cameras.iter_mut() {
+        commands.entity(camera).remove::<(CameraRig, CameraShake)>();
+        *transform = presets.menu;
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::{prelude::*, render::camera::ScalingMode, window::PrimaryWindow};
+
+use super::CameraSystems;
+
+pub struct PixelCameraPlugin;
+
+impl Plugin for PixelCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_pixel_scale).add_systems(
+            PostUpdate,
+            snap_to_pixels
+                .in_set(CameraSystems)
+                .after(super::shake::apply_shake),
+        );
+    }
+}
+
+/// Scales an orthographic camera by the largest whole number that still fits `resolution` in the window,
+/// and snaps it to whole world units - so one world unit is always a whole number of screen pixels.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PixelPerfect {
+    /// The area, in world units, that must always be visible
+    pub resolution: UVec2,
+}
+
+impl Default for PixelPerfect {
+    fn default() -> Self {
+        Self {
+            resolution: UVec2::new(320, 180),
+        }
+    }
+}
+
+impl PixelPerfect {
+    /// The number of physical pixels per world unit for a window of the given physical size.
+    pub fn scale_for(&self, window: UVec2) -> u32 {
+        let fit = window / self.resolution.max(UVec2::ONE);
+        fit.min_element().max(1)
+    }
+}
+
+fn update_pixel_scale(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&PixelPerfect, &mut OrthographicProjection)>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let size = UVec2::new(window.physical_width(), window.physical_height());
+    // The projection works in logical pixels, so the scale factor is divided back out.
+    let factor = window.scale_factor() as f32;
+
+    for (pixel, mut projection) in cameras.iter_mut() {
+        let scale = pixel.scale_for(size) as f32 / factor;
+        let unchanged = matches!(projection.scaling_mode, ScalingMode::WindowSize(s) if (s - scale).abs() < f32::EPSILON);
+        if !unchanged {
+            projection.scaling_mode = ScalingMode::WindowSize(scale);
+        }
+    }
+}
+
+fn snap_to_pixels(mut cameras: Query<(&mut Transform, &mut GlobalTransform), With<PixelPerfect>>) {
+    for (mut transform, mut global) in cameras.iter_mut() {
+        let snapped = transform.translation.truncate().round();
+        if snapped != transform.translation.truncate() {
+            transform.translation = snapped.extend(transform.translation.z);
+            *global = GlobalTransform::from(*transform);
+        }
+    }
+}
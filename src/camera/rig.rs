@@ -0,0 +1,241 @@
+use bevy::{input::mouse::MouseMotion, prelude::*};
+
+use crate::in_game::game_state::PauseState;
+
+use super::CameraSystems;
+
+pub struct CameraRigPlugin;
+
+impl Plugin for CameraRigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, orbit_input.run_if(in_state(PauseState::None)))
+            .add_systems(PostUpdate, update_rigs.in_set(CameraSystems));
+    }
+}
+
+/// Marks the entity the camera rig follows.
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct CameraTarget;
+
+#[derive(Clone, Copy, Debug)]
+pub enum RigMode {
+    /// Looks down at the target from above, pulled back by `tilt` along the Z axis
+    TopDown { height: f32, tilt: f32 },
+    /// Sits behind the target, following its rotation
+    ThirdPerson { distance: f32, height: f32 },
+    /// Circles the target - dragging with the right mouse button changes the yaw & pitch
+    Orbit {
+        distance: f32,
+        yaw: f32,
+        pitch: f32,
+        sensitivity: f32,
+    },
+    /// Looks along the Z axis from a fixed `distance`, following the target in X & Y only
+    SideScroller { distance: f32, height: f32 },
+    /// Sits `height` above the target, looking the way it faces
+    FirstPerson { height: f32 },
+}
+
+/// Limits on where the camera's focus point can go.
+#[derive(Clone, Copy, Debug)]
+pub struct CameraBounds {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct CameraRig {
+    pub mode: RigMode,
+    /// How quickly the camera catches up with the target - higher is snappier, and 0 disables smoothing
+    pub smoothing: f32,
+    /// How far the target can get from the focus point on each axis before the camera follows
+    pub dead_zone: Vec3,
+    pub bounds: Option<CameraBounds>,
+    focus: Option<Vec3>,
+    base: Option<Transform>,
+}
+
+impl Default for CameraRig {
+    fn default() -> Self {
+        Self::new(RigMode::ThirdPerson {
+            distance: 6.,
+            height: 3.,
+        })
+    }
+}
+
+impl CameraRig {
+    pub fn new(mode: RigMode) -> Self {
+        Self {
+            mode,
+            smoothing: 8.,
+            dead_zone: Vec3::ZERO,
+            bounds: None,
+            focus: None,
+            base: None,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn top_down(height: f32, tilt: f32) -> Self {
+        Self::new(RigMode::TopDown { height, tilt })
+    }
+
+    #[allow(dead_code)]
+    pub fn third_person(distance: f32, height: f32) -> Self {
+        Self::new(RigMode::ThirdPerson { distance, height })
+    }
+
+    #[allow(dead_code)]
+    pub fn orbit(distance: f32) -> Self {
+        Self::new(RigMode::Orbit {
+            distance,
+            yaw: 0.,
+            pitch: 0.4,
+            sensitivity: 0.005,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn side_scroller(distance: f32, height: f32) -> Self {
+        Self::new(RigMode::SideScroller { distance, height })
+    }
+
+    /// Follows the target without smoothing, so looking around stays responsive.
+    #[allow(dead_code)]
+    pub fn first_person(height: f32) -> Self {
+        Self::new(RigMode::FirstPerson { height }).with_smoothing(0.)
+    }
+
+    pub fn with_smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_dead_zone(mut self, dead_zone: Vec3) -> Self {
+        self.dead_zone = dead_zone;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_bounds(mut self, min: Vec3, max: Vec3) -> Self {
+        self.bounds = Some(CameraBounds { min, max });
+        self
+    }
+
+    /// The camera's transform before any effects, such as shake, are applied.
+    pub fn base(&self) -> Option<Transform> {
+        self.base
+    }
+
+    fn update_focus(&mut self, target: Vec3) -> Vec3 {
+        let mut focus = self.focus.unwrap_or(target);
+        let offset = target - focus;
+        let outside = offset.abs() - self.dead_zone;
+        focus += Vec3::select(
+            outside.cmpgt(Vec3::ZERO),
+            offset.signum() * outside,
+            Vec3::ZERO,
+        );
+        if let Some(bounds) = self.bounds {
+            focus = focus.clamp(bounds.min, bounds.max);
+        }
+        self.focus = Some(focus);
+        focus
+    }
+
+    fn desired_transform(&self, focus: Vec3, target_rotation: Quat) -> Transform {
+        match self.mode {
+            RigMode::TopDown { height, tilt } => {
+                let up = if tilt.abs() < f32::EPSILON {
+                    Vec3::NEG_Z
+                } else {
+                    Vec3::Y
+                };
+                Transform::from_translation(focus + Vec3::new(0., height, tilt))
+                    .looking_at(focus, up)
+            }
+            RigMode::ThirdPerson { distance, height } => {
+                let behind = target_rotation * Vec3::Z;
+                let behind = Vec3::new(behind.x, 0., behind.z).normalize_or_zero();
+                Transform::from_translation(focus + behind * distance + Vec3::Y * height)
+                    .looking_at(focus, Vec3::Y)
+            }
+            RigMode::Orbit {
+                distance,
+                yaw,
+                pitch,
+                ..
+            } => {
+                let rotation = Quat::from_euler(EulerRot::YXZ, yaw, -pitch, 0.);
+                Transform::from_translation(focus + rotation * Vec3::Z * distance)
+                    .looking_at(focus, Vec3::Y)
+            }
+            // The target's depth is its draw order in 2D, so the camera's depth stays put
+            RigMode::SideScroller { distance, height } => {
+                Transform::from_xyz(focus.x, focus.y + height, distance)
+            }
+            RigMode::FirstPerson { height } => {
+                Transform::from_translation(focus + Vec3::Y * height).with_rotation(target_rotation)
+            }
+        }
+    }
+}
+
+pub(super) fn update_rigs(
+    time: Res<Time>,
+    targets: Query<&GlobalTransform, (With<CameraTarget>, Without<CameraRig>)>,
+    mut cameras: Query<(&mut CameraRig, &mut Transform, &mut GlobalTransform)>,
+) {
+    let target = targets.iter().next().map(|t| t.compute_transform());
+
+    for (mut rig, mut transform, mut global) in cameras.iter_mut() {
+        let previous = rig.base.unwrap_or(*transform);
+        let Some(target) = target else {
+            rig.base = Some(previous);
+            continue;
+        };
+
+        let focus = rig.update_focus(target.translation);
+        let desired = rig.desired_transform(focus, target.rotation);
+
+        let next = if rig.smoothing <= 0. || rig.base.is_none() {
+            desired
+        } else {
+            let t = 1. - (-rig.smoothing * time.delta_seconds()).exp();
+            Transform {
+                translation: previous.translation.lerp(desired.translation, t),
+                rotation: previous.rotation.slerp(desired.rotation, t),
+                scale: desired.scale,
+            }
+        };
+
+        rig.base = Some(next);
+        *transform = next;
+        *global = GlobalTransform::from(next);
+    }
+}
+
+fn orbit_input(
+    buttons: Res<Input<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    mut rigs: Query<&mut CameraRig>,
+) {
+    let delta: Vec2 = motion.read().map(|m| m.delta).sum();
+    if !buttons.pressed(MouseButton::Right) || delta == Vec2::ZERO {
+        return;
+    }
+    for mut rig in rigs.iter_mut() {
+        if let RigMode::Orbit {
+            yaw,
+            pitch,
+            sensitivity,
+            ..
+        } = &mut rig.mode
+        {
+            *yaw -= delta.x * *sensitivity;
+            *pitch = (*pitch + delta.y * *sensitivity).clamp(-1.4, 1.4);
+        }
+    }
+}
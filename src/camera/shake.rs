@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use super::{CameraRig, CameraSystems};
+
+pub struct CameraShakePlugin;
+
+impl Plugin for CameraShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            apply_shake
+                .in_set(CameraSystems)
+                .after(super::rig::update_rigs),
+        );
+    }
+}
+
+/// Trauma based camera shake - the shake grows with the square of the trauma,
+/// which decays over time.
+#[derive(Component, Clone, Debug)]
+pub struct CameraShake {
+    pub trauma: f32,
+    /// How much trauma is lost per second
+    pub decay: f32,
+    /// The largest offset, in world units, at full trauma
+    pub max_offset: f32,
+    /// The largest roll, in radians, at full trauma
+    pub max_roll: f32,
+    pub frequency: f32,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.,
+            decay: 1.5,
+            max_offset: 0.3,
+            max_roll: 0.05,
+            frequency: 15.,
+        }
+    }
+}
+
+impl CameraShake {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0., 1.);
+    }
+}
+
+/// Cheap smooth noise in the range [-1, 1], from a sum of out of phase sine waves.
+fn noise(t: f32, seed: f32) -> f32 {
+    ((t + seed).sin() + (t * 2.3 + seed * 1.7).sin() * 0.5 + (t * 5.1 + seed * 3.1).sin() * 0.25)
+        / 1.75
+}
+
+pub(super) fn apply_shake(
+    time: Res<Time<Real>>,
+    mut cameras: Query<(
+        &CameraRig,
+        &mut CameraShake,
+        &mut Transform,
+        &mut GlobalTransform,
+    )>,
+) {
+    for (rig, mut shake, mut transform, mut global) in cameras.iter_mut() {
+        let decay = shake.decay * time.delta_seconds();
+        shake.trauma = (shake.trauma - decay).max(0.);
+        if shake.trauma <= 0. {
+            continue;
+        }
+        let Some(base) = rig.base() else {
+            continue;
+        };
+
+        let amount = shake.trauma * shake.trauma;
+        let t = time.elapsed_seconds() * shake.frequency;
+        let offset = Vec3::new(noise(t, 0.), noise(t, 10.), 0.) * shake.max_offset * amount;
+        let roll = noise(t, 20.) * shake.max_roll * amount;
+
+        *transform = Transform {
+            translation: base.translation + base.rotation * offset,
+            rotation: base.rotation * Quat::from_rotation_z(roll),
+            scale: base.scale,
+        };
+        *global = GlobalTransform::from(*transform);
+    }
+}
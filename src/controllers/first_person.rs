@@ -0,0 +1,101 @@
+use bevy::{
+    prelude::*,
+    window::{CursorGrabMode, PrimaryWindow},
+};
+use leafwing_input_manager::prelude::*;
+
+use crate::{app_state::AppState, health::Respawning, in_game::game_state::PauseState};
+
+use super::PlayerAction;
+
+/// Walks on the XZ plane and looks around with the mouse, for 3D games.
+/// Pair it with [`crate::camera::CameraRig::first_person`] and a `CameraTarget`.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct FirstPersonController {
+    /// In units per second
+    pub speed: f32,
+    /// Radians turned per pixel of mouse movement
+    pub sensitivity: f32,
+    /// How far up or down it can look, in radians
+    pub pitch_limit: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl Default for FirstPersonController {
+    fn default() -> Self {
+        Self {
+            speed: 4.,
+            sensitivity: 0.003,
+            pitch_limit: 1.5,
+            yaw: 0.,
+            pitch: 0.,
+        }
+    }
+}
+
+pub(super) fn look(
+    mut controllers: Query<
+        (
+            &mut FirstPersonController,
+            &ActionState<PlayerAction>,
+            &mut Transform,
+        ),
+        Without<Respawning>,
+    >,
+) {
+    for (mut controller, action_state, mut transform) in controllers.iter_mut() {
+        let look = action_state
+            .axis_pair(PlayerAction::Look)
+            .map(|axis| axis.xy())
+            .unwrap_or_default();
+        let sensitivity = controller.sensitivity;
+        controller.yaw -= look.x * sensitivity;
+        controller.pitch = (controller.pitch - look.y * sensitivity)
+            .clamp(-controller.pitch_limit, controller.pitch_limit);
+        transform.rotation = Quat::from_euler(EulerRot::YXZ, controller.yaw, controller.pitch, 0.);
+    }
+}
+
+pub(super) fn movement(
+    time: Res<Time>,
+    mut controllers: Query<
+        (
+            &FirstPersonController,
+            &ActionState<PlayerAction>,
+            &mut Transform,
+        ),
+        Without<Respawning>,
+    >,
+) {
+    for (controller, action_state, mut transform) in controllers.iter_mut() {
+        let input = PlayerAction::movement(action_state);
+        let yaw = Quat::from_rotation_y(controller.yaw);
+        let direction = yaw * Vec3::new(input.x, 0., -input.y);
+        transform.translation += direction * controller.speed * time.delta_seconds();
+    }
+}
+
+/// Locks the cursor while a first person controller is being played, and frees it otherwise.
+pub(super) fn grab_cursor(
+    app_state: Res<State<AppState>>,
+    pause_state: Res<State<PauseState>>,
+    controllers: Query<(), With<FirstPersonController>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let grab = *app_state.get() == AppState::InGame
+        && *pause_state.get() == PauseState::None
+        && !controllers.is_empty();
+    let (mode, visible) = if grab {
+        (CursorGrabMode::Locked, false)
+    } else {
+        (CursorGrabMode::None, true)
+    };
+    for mut window in windows.iter_mut() {
+        if window.cursor.grab_mode != mode || window.cursor.visible != visible {
+            window.cursor.grab_mode = mode;
+            window.cursor.visible = visible;
+        }
+    }
+}
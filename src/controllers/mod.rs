@@ -0,0 +1,104 @@
+//! Ready-made player controllers, driven by [`PlayerAction`]s rather than raw input.
+//!
+//! Add one of the controller components to an entity and it gets the default input map if it
+//! doesn't already have one. The 2D controllers move bodies through the physics layer, so they
+//! need a [`crate::physics::Collider`] too. Like the rest of the game, controllers stop while paused.
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+use crate::{
+    in_game::{InGameFixedUpdate, InGameUpdate},
+    physics::PhysicsSet,
+};
+
+mod first_person;
+mod platformer;
+mod top_down;
+
+pub use first_person::FirstPersonController;
+pub use platformer::PlatformerController;
+pub use top_down::TopDownController;
+
+pub struct ControllersPlugin;
+
+impl Plugin for ControllersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<PlayerAction>::default())
+            .register_type::<TopDownController>()
+            .register_type::<PlatformerController>()
+            .register_type::<FirstPersonController>()
+            .add_systems(
+                Update,
+                (
+                    add_player_input::<TopDownController>,
+                    add_player_input::<PlatformerController>,
+                    add_player_input::<FirstPersonController>,
+                    first_person::grab_cursor,
+                ),
+            )
+            .add_systems(
+                InGameUpdate,
+                (
+                    platformer::buffer_jumps,
+                    (first_person::look, first_person::movement).chain(),
+                ),
+            )
+            .add_systems(
+                InGameFixedUpdate,
+                (top_down::movement, platformer::movement)
+                    .after(PhysicsSet::Prepare)
+                    .before(PhysicsSet::Step),
+            );
+    }
+}
+
+#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect)]
+pub enum PlayerAction {
+    Move,
+    Jump,
+    Look,
+}
+
+impl PlayerAction {
+    /// Keyboard, mouse and gamepad bindings for every action.
+    pub fn default_input_map() -> InputMap<Self> {
+        let mut map = InputMap::default();
+        map.insert(VirtualDPad::wasd(), Self::Move)
+            .insert(VirtualDPad::arrow_keys(), Self::Move)
+            .insert(DualAxis::left_stick(), Self::Move)
+            .insert(KeyCode::Space, Self::Jump)
+            .insert(GamepadButtonType::South, Self::Jump)
+            .insert(DualAxis::mouse_motion(), Self::Look);
+        map
+    }
+
+    /// The movement input, no longer than 1 so diagonals aren't faster.
+    pub fn movement(action_state: &ActionState<Self>) -> Vec2 {
+        action_state
+            .axis_pair(Self::Move)
+            .map(|axis| axis.xy().clamp_length_max(1.))
+            .unwrap_or_default()
+    }
+}
+
+fn add_player_input<C: Component>(
+    mut commands: Commands,
+    controllers: Query<Entity, (With<C>, Without<ActionState<PlayerAction>>)>,
+) {
+    for entity in controllers.iter() {
+        commands.entity(entity).insert(InputManagerBundle {
+            action_state: ActionState::default(),
+            input_map: PlayerAction::default_input_map(),
+        });
+    }
+}
+
+/// Moves `current` towards `target` by at most `max_delta`.
+fn move_towards(current: Vec2, target: Vec2, max_delta: f32) -> Vec2 {
+    let difference = target - current;
+    if difference.length() <= max_delta {
+        target
+    } else {
+        current + difference.normalize() * max_delta
+    }
+}
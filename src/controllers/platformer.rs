@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+use crate::{
+    health::Respawning,
+    physics::{Contacts, Velocity},
+};
+
+use super::{move_towards, PlayerAction};
+
+/// Runs and jumps in a side view. Needs a dynamic body so gravity pulls it down.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct PlatformerController {
+    /// In units per second
+    pub speed: f32,
+    /// How quickly it speeds up and stops on the ground, in units per second squared
+    pub acceleration: f32,
+    /// How quickly it speeds up and stops in the air
+    pub air_acceleration: f32,
+    /// The upwards speed of a jump
+    pub jump_speed: f32,
+    /// How much of the upwards speed is kept when jump is released early, for shorter hops
+    pub jump_cut: f32,
+    /// How long after walking off a ledge a jump still works, in seconds
+    pub coyote_time: f32,
+    /// How long before landing a jump press is remembered, in seconds
+    pub jump_buffer: f32,
+}
+
+impl Default for PlatformerController {
+    fn default() -> Self {
+        Self {
+            speed: 120.,
+            acceleration: 1200.,
+            air_acceleration: 600.,
+            jump_speed: 300.,
+            jump_cut: 0.5,
+            coyote_time: 0.1,
+            jump_buffer: 0.1,
+        }
+    }
+}
+
+#[derive(Component, Default)]
+pub(super) struct PlatformerState {
+    /// Time left to jump after leaving the ground
+    coyote: f32,
+    /// Time left for a buffered jump press to be used
+    buffered_jump: f32,
+    /// Still rising from a jump that can be cut short
+    jumping: bool,
+}
+
+type JumpBufferQuery<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'a PlatformerController,
+        &'a ActionState<PlayerAction>,
+        Option<&'a mut PlatformerState>,
+    ),
+    Without<Respawning>,
+>;
+
+type PlatformerQuery<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'a PlatformerController,
+        &'a ActionState<PlayerAction>,
+        &'a mut PlatformerState,
+        Option<&'a mut Velocity>,
+        Option<&'a Contacts>,
+    ),
+    Without<Respawning>,
+>;
+
+/// Jump presses are caught every frame, so none are missed between fixed steps.
+pub(super) fn buffer_jumps(mut commands: Commands, mut controllers: JumpBufferQuery) {
+    for (entity, controller, action_state, state) in controllers.iter_mut() {
+        let Some(mut state) = state else {
+            commands.entity(entity).insert(PlatformerState::default());
+            continue;
+        };
+        if action_state.just_pressed(PlayerAction::Jump) {
+            state.buffered_jump = controller.jump_buffer;
+        }
+    }
+}
+
+pub(super) fn movement(mut commands: Commands, time: Res<Time>, mut controllers: PlatformerQuery) {
+    let delta = time.delta_seconds();
+    for (entity, controller, action_state, mut state, velocity, contacts) in controllers.iter_mut()
+    {
+        let Some(mut velocity) = velocity else {
+            commands.entity(entity).insert(Velocity::default());
+            continue;
+        };
+        let grounded = contacts.is_some_and(Contacts::on_ground);
+
+        if grounded {
+            state.coyote = controller.coyote_time;
+            if velocity.y <= 0. {
+                state.jumping = false;
+            }
+        } else {
+            state.coyote = (state.coyote - delta).max(0.);
+        }
+        state.buffered_jump = (state.buffered_jump - delta).max(0.);
+
+        if state.buffered_jump > 0. && state.coyote > 0. {
+            velocity.y = controller.jump_speed;
+            state.buffered_jump = 0.;
+            state.coyote = 0.;
+            state.jumping = true;
+        } else if state.jumping && !action_state.pressed(PlayerAction::Jump) {
+            if velocity.y > 0. {
+                velocity.y *= controller.jump_cut;
+            }
+            state.jumping = false;
+        }
+
+        let acceleration = if grounded {
+            controller.acceleration
+        } else {
+            controller.air_acceleration
+        };
+        let target = PlayerAction::movement(action_state).x * controller.speed;
+        velocity.x = move_towards(
+            Vec2::new(velocity.x, 0.),
+            Vec2::new(target, 0.),
+            acceleration * delta,
+        )
+        .x;
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+use crate::{health::Respawning, physics::Velocity};
+
+use super::{move_towards, PlayerAction};
+
+/// Moves in eight directions, with the same speed on the diagonals.
+/// Works best on a kinematic body, with gravity turned off.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct TopDownController {
+    /// In units per second
+    pub speed: f32,
+    /// How quickly it gets up to speed and stops, in units per second squared
+    pub acceleration: f32,
+}
+
+impl Default for TopDownController {
+    fn default() -> Self {
+        Self {
+            speed: 100.,
+            acceleration: 1000.,
+        }
+    }
+}
+
+type TopDownQuery<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'a TopDownController,
+        &'a ActionState<PlayerAction>,
+        Option<&'a mut Velocity>,
+    ),
+    Without<Respawning>,
+>;
+
+pub(super) fn movement(mut commands: Commands, time: Res<Time>, mut controllers: TopDownQuery) {
+    for (entity, controller, action_state, velocity) in controllers.iter_mut() {
+        let target = PlayerAction::movement(action_state) * controller.speed;
+        let Some(mut velocity) = velocity else {
+            commands.entity(entity).insert(Velocity::default());
+            continue;
+        };
+        velocity.0 = move_towards(
+            velocity.0,
+            target,
+            controller.acceleration * time.delta_seconds(),
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use bevy::{prelude::*, reflect::TypePath, utils::HashMap};
+use serde::Deserialize;
+
+/// A conversation made of named nodes, loaded from a `.dialogue.yaml` file.
+#[derive(Deserialize, TypePath, Asset, Clone, Debug)]
+pub struct Dialogue {
+    /// The node the conversation starts at, unless another one is asked for
+    #[serde(default = "default_start")]
+    pub start: String,
+    pub nodes: HashMap<String, DialogueNode>,
+}
+
+fn default_start() -> String {
+    "start".to_string()
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct DialogueNode {
+    /// Shown one at a time, skipping any whose condition isn't met
+    pub lines: Vec<DialogueLine>,
+    /// Offered after the last line - if none are available, the conversation moves on to `next`
+    pub choices: Vec<DialogueChoice>,
+    /// The node after this one - the conversation ends without one
+    pub next: Option<String>,
+    /// Set when the conversation leaves the node, so its own lines and choices don't see them
+    pub set: HashMap<String, DialogueValue>,
+    /// Sent as [`super::DialogueSignal`]s when the conversation leaves the node
+    pub signals: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct DialogueLine {
+    #[serde(default)]
+    pub speaker: Option<String>,
+    pub text: String,
+    #[serde(default)]
+    pub condition: Option<Condition>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct DialogueChoice {
+    pub text: String,
+    /// Ends the conversation if there isn't one
+    #[serde(default)]
+    pub next: Option<String>,
+    /// Hides the choice unless it's met
+    #[serde(default)]
+    pub condition: Option<Condition>,
+    /// Variables set when the choice is picked, after the node's own
+    #[serde(default)]
+    pub set: HashMap<String, DialogueValue>,
+    /// Sent as [`super::DialogueSignal`]s when the choice is picked
+    #[serde(default)]
+    pub signals: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum DialogueValue {
+    Bool(bool),
+    Number(f32),
+    Text(String),
+}
+
+/// A check against the [`super::DialogueVariables`], written like `{ is: met_keeper }` or
+/// `{ at_least: { variable: coins, value: 3 } }`.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    /// The variable is set to true
+    Is(String),
+    /// The variable is unset or false
+    Not(String),
+    Equals {
+        variable: String,
+        value: DialogueValue,
+    },
+    AtLeast {
+        variable: String,
+        value: f32,
+    },
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
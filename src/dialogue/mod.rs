@@ -0,0 +1,366 @@
+//! Branching conversations, written as `.dialogue.yaml` assets.
+//!
+//! A [`Dialogue`] is a set of named nodes, each with lines from its speakers and choices for the
+//! player, both of which can depend on the [`DialogueVariables`]. A node's own `set` and `signals`
+//! take effect as the conversation leaves it, before those of the picked choice, so its lines and
+//! choices see the variables from before it was reached. [`StartDialogue`] puts the game in
+//! [`PauseState::Dialogue`], which suspends gameplay like pausing does until the conversation ends.
+//! A [`Sensor`] with a [`DialogueTrigger`] starts one when the player walks into it.
+use bevy::{asset::LoadState, ecs::system::Command, prelude::*, utils::HashMap};
+use bevy_common_assets::yaml::YamlAssetPlugin;
+
+use crate::{
+    app_state::AppState,
+    health::Team,
+    in_game::game_state::PauseState,
+    physics::{Sensor, TriggerEntered},
+};
+
+mod asset;
+mod ui;
+
+pub use asset::{Condition, Dialogue, DialogueValue};
+
+/// How many nodes a conversation can pass through without showing anything before it's abandoned,
+/// so nodes that lead back to each other can't hang the game.
+const MAX_EMPTY_NODES: usize = 64;
+
+pub struct DialoguePlugin;
+
+impl Plugin for DialoguePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(YamlAssetPlugin::<Dialogue>::new(&["dialogue.yaml"]))
+            .init_resource::<DialogueVariables>()
+            .add_event::<DialogueStarted>()
+            .add_event::<DialogueSignal>()
+            .add_event::<DialogueEnded>()
+            .add_event::<AdvanceDialogue>()
+            .register_type::<DialogueTrigger>()
+            .add_systems(
+                Update,
+                trigger_dialogue.run_if(not(resource_exists::<ActiveDialogue>())),
+            )
+            .add_systems(
+                Update,
+                advance_dialogue
+                    .run_if(in_state(PauseState::Dialogue))
+                    .run_if(resource_exists::<ActiveDialogue>()),
+            )
+            .add_systems(OnExit(AppState::InGame), clear_dialogue)
+            .add_plugins(ui::DialogueUiPlugin);
+    }
+}
+
+/// The values dialogue conditions check, set by the dialogue itself or by gameplay code.
+/// They last until the player leaves the game.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct DialogueVariables(pub HashMap<String, DialogueValue>);
+
+impl DialogueVariables {
+    pub fn get(&self, variable: &str) -> Option<&DialogueValue> {
+        self.0.get(variable)
+    }
+
+    pub fn set(&mut self, variable: impl Into<String>, value: DialogueValue) {
+        self.0.insert(variable.into(), value);
+    }
+
+    pub fn is_true(&self, variable: &str) -> bool {
+        matches!(self.get(variable), Some(DialogueValue::Bool(true)))
+    }
+
+    pub fn check(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Is(variable) => self.is_true(variable),
+            Condition::Not(variable) => !self.is_true(variable),
+            Condition::Equals { variable, value } => self.get(variable) == Some(value),
+            Condition::AtLeast { variable, value } => {
+                matches!(self.get(variable), Some(DialogueValue::Number(n)) if n >= value)
+            }
+            Condition::All(conditions) => conditions.iter().all(|c| self.check(c)),
+            Condition::Any(conditions) => conditions.iter().any(|c| self.check(c)),
+        }
+    }
+
+    fn check_optional(&self, condition: &Option<Condition>) -> bool {
+        match condition {
+            Some(condition) => self.check(condition),
+            None => true,
+        }
+    }
+}
+
+/// Starts the conversation in the dialogue at `path`, from `node` or the dialogue's start node.
+pub struct StartDialogue {
+    pub path: String,
+    pub node: Option<String>,
+}
+
+#[allow(dead_code)]
+impl StartDialogue {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            node: None,
+        }
+    }
+
+    pub fn at(mut self, node: impl Into<String>) -> Self {
+        self.node = Some(node.into());
+        self
+    }
+}
+
+impl Command for StartDialogue {
+    fn apply(self, world: &mut World) {
+        let handle = world.resource::<AssetServer>().load(&self.path);
+        world.insert_resource(ActiveDialogue {
+            path: self.path.clone(),
+            handle,
+            node: self.node,
+            line: 0,
+            started: false,
+            step: None,
+        });
+        world.insert_resource(NextState(Some(PauseState::Dialogue)));
+        world.send_event(DialogueStarted { path: self.path });
+    }
+}
+
+/// The conversation being shown, and where it's up to.
+#[derive(Resource, Debug)]
+pub struct ActiveDialogue {
+    pub path: String,
+    handle: Handle<Dialogue>,
+    node: Option<String>,
+    line: usize,
+    /// Whether the start node has been picked, which waits for the asset to load
+    started: bool,
+    step: Option<DialogueStep>,
+}
+
+impl ActiveDialogue {
+    #[allow(dead_code)]
+    pub fn node(&self) -> Option<&str> {
+        self.node.as_deref()
+    }
+
+    /// What's on screen - [`None`] until the dialogue has loaded.
+    pub fn step(&self) -> Option<&DialogueStep> {
+        self.step.as_ref()
+    }
+
+    fn go_to(&mut self, node: Option<String>) {
+        self.node = node;
+        self.line = 0;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum DialogueStep {
+    Line {
+        speaker: Option<String>,
+        text: String,
+    },
+    /// The available choices, by their index in the node
+    Choices(Vec<(usize, String)>),
+}
+
+/// Starts a conversation when an entity on [`Team::Player`] enters this entity's [`Sensor`].
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct DialogueTrigger {
+    pub path: String,
+    pub node: Option<String>,
+}
+
+fn trigger_dialogue(
+    mut commands: Commands,
+    mut triggers: EventReader<TriggerEntered>,
+    dialogues: Query<&DialogueTrigger, With<Sensor>>,
+    teams: Query<&Team>,
+) {
+    // Only one conversation at a time
+    let Some(dialogue) = triggers
+        .read()
+        .filter(|trigger| teams.get(trigger.entity) == Ok(&Team::Player))
+        .filter_map(|trigger| dialogues.get(trigger.sensor).ok())
+        .last()
+    else {
+        return;
+    };
+    commands.add(StartDialogue {
+        path: dialogue.path.clone(),
+        node: dialogue.node.clone(),
+    });
+}
+
+/// Sent when [`StartDialogue`] is applied.
+#[derive(Event, Clone, Debug)]
+#[allow(dead_code)]
+pub struct DialogueStarted {
+    pub path: String,
+}
+
+/// A named hook for gameplay code, listed under `signals` in a node or choice.
+#[derive(Event, Clone, Debug)]
+#[allow(dead_code)]
+pub struct DialogueSignal {
+    pub name: String,
+    pub node: String,
+}
+
+#[derive(Event, Clone, Debug)]
+#[allow(dead_code)]
+pub struct DialogueEnded {
+    pub path: String,
+}
+
+/// Moves the dialogue past the current line, or picks a choice when it's showing choices.
+#[derive(Event, Clone, Copy, Debug)]
+pub(crate) struct AdvanceDialogue(pub Option<usize>);
+
+#[allow(clippy::too_many_arguments)]
+fn advance_dialogue(
+    mut commands: Commands,
+    mut active: ResMut<ActiveDialogue>,
+    dialogues: Res<Assets<Dialogue>>,
+    asset_server: Res<AssetServer>,
+    mut variables: ResMut<DialogueVariables>,
+    mut advance: EventReader<AdvanceDialogue>,
+    mut signals: EventWriter<DialogueSignal>,
+    mut ended: EventWriter<DialogueEnded>,
+) {
+    let Some(dialogue) = dialogues.get(&active.handle) else {
+        if asset_server.get_load_state(&active.handle) == Some(LoadState::Failed) {
+            error!("Couldn't load dialogue {}", active.path);
+            end_dialogue(&mut commands, &active, &mut ended);
+        }
+        return;
+    };
+
+    if !active.started {
+        active.started = true;
+        if active.node.is_none() {
+            active.node = Some(dialogue.start.clone());
+        }
+    } else {
+        let Some(event) = advance.read().last().copied() else {
+            return;
+        };
+        let choosing = matches!(active.step, Some(DialogueStep::Choices(_)));
+        match (choosing, event.0) {
+            (true, Some(index)) => {
+                let name = active.node.clone().unwrap_or_default();
+                let Some(node) = dialogue.nodes.get(&name) else {
+                    return;
+                };
+                let Some(choice) = node.choices.get(index) else {
+                    return;
+                };
+                apply_effects(
+                    &node.set,
+                    &node.signals,
+                    &name,
+                    &mut variables,
+                    &mut signals,
+                );
+                apply_effects(
+                    &choice.set,
+                    &choice.signals,
+                    &name,
+                    &mut variables,
+                    &mut signals,
+                );
+                active.go_to(choice.next.clone());
+            }
+            // Choices have to be picked, not skipped
+            (true, None) => return,
+            (false, _) => {}
+        }
+    }
+    advance.clear();
+
+    match next_step(dialogue, &mut active, &mut variables, &mut signals) {
+        Some(step) => active.step = Some(step),
+        None => end_dialogue(&mut commands, &active, &mut ended),
+    }
+}
+
+/// Follows the dialogue from where it's up to until there's something to show.
+fn next_step(
+    dialogue: &Dialogue,
+    active: &mut ActiveDialogue,
+    variables: &mut DialogueVariables,
+    signals: &mut EventWriter<DialogueSignal>,
+) -> Option<DialogueStep> {
+    for _ in 0..MAX_EMPTY_NODES {
+        let name = active.node.clone()?;
+        let Some(node) = dialogue.nodes.get(&name) else {
+            warn!("Dialogue {} has no node named {name}", active.path);
+            return None;
+        };
+        while let Some(line) = node.lines.get(active.line) {
+            active.line += 1;
+            if variables.check_optional(&line.condition) {
+                return Some(DialogueStep::Line {
+                    speaker: line.speaker.clone(),
+                    text: line.text.clone(),
+                });
+            }
+        }
+
+        let choices = node
+            .choices
+            .iter()
+            .enumerate()
+            .filter(|(_, choice)| variables.check_optional(&choice.condition))
+            .map(|(index, choice)| (index, choice.text.clone()))
+            .collect::<Vec<_>>();
+        if !choices.is_empty() {
+            return Some(DialogueStep::Choices(choices));
+        }
+        apply_effects(&node.set, &node.signals, &name, variables, signals);
+        active.go_to(node.next.clone());
+    }
+    warn!(
+        "Dialogue {} went through too many nodes without showing anything",
+        active.path
+    );
+    None
+}
+
+fn apply_effects(
+    set: &HashMap<String, DialogueValue>,
+    names: &[String],
+    node: &str,
+    variables: &mut DialogueVariables,
+    signals: &mut EventWriter<DialogueSignal>,
+) {
+    for (variable, value) in set.iter() {
+        variables.set(variable, value.clone());
+    }
+    for name in names.iter() {
+        signals.send(DialogueSignal {
+            name: name.clone(),
+            node: node.to_string(),
+        });
+    }
+}
+
+fn end_dialogue(
+    commands: &mut Commands,
+    active: &ActiveDialogue,
+    ended: &mut EventWriter<DialogueEnded>,
+) {
+    ended.send(DialogueEnded {
+        path: active.path.clone(),
+    });
+    commands.remove_resource::<ActiveDialogue>();
+    commands.insert_resource(NextState(Some(PauseState::None)));
+}
+
+fn clear_dialogue(mut commands: Commands, mut variables: ResMut<DialogueVariables>) {
+    commands.remove_resource::<ActiveDialogue>();
+    variables.0.clear();
+}
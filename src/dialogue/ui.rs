@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+use bevy_ui_dsl::*;
+use bevy_ui_navigation::prelude::NavRequest;
+use dexterous_developer::{
+    dexterous_developer_setup, ReloadableApp, ReloadableAppContents, ReloadableElementsSetup,
+};
+
+use crate::{
+    in_game::game_state::PauseState,
+    ui::{
+        buttons::{focus_text_button, focused_button_activated, TypedFocusedButtonQuery},
+        classes::*,
+        intermediary_node_bundles::*,
+    },
+};
+
+use super::{ActiveDialogue, AdvanceDialogue, DialogueStep};
+
+const CHARACTERS_PER_SECOND: f32 = 40.;
+
+pub(super) struct DialogueUiPlugin;
+
+impl Plugin for DialogueUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(PauseState::Dialogue), exit)
+            .setup_reloadable_elements::<reloadable>();
+    }
+}
+
+#[dexterous_developer_setup(dialogue)]
+fn reloadable(app: &mut ReloadableAppContents) {
+    app.add_systems(
+        Update,
+        (
+            show_step
+                .after(super::advance_dialogue)
+                .run_if(resource_exists_and_changed::<ActiveDialogue>()),
+            type_text,
+            focused_button_activated.pipe(process_input),
+        )
+            .chain()
+            .run_if(in_state(PauseState::Dialogue)),
+    );
+}
+
+#[derive(Component)]
+struct DialogueBox;
+
+#[derive(Component, Clone, Copy)]
+enum Buttons {
+    Continue,
+    Choice(usize),
+}
+
+/// Reveals a line of text a few characters at a time.
+#[derive(Component)]
+struct Typewriter {
+    text: String,
+    shown: f32,
+}
+
+impl Typewriter {
+    fn finished(&self) -> bool {
+        self.shown as usize >= self.text.chars().count()
+    }
+}
+
+fn show_step(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    active: Res<ActiveDialogue>,
+    boxes: Query<Entity, With<DialogueBox>>,
+    mut nav: EventWriter<NavRequest>,
+) {
+    let Some(step) = active.step() else {
+        return;
+    };
+    for entity in boxes.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let mut line = None;
+    let mut buttons = vec![];
+    let r = root(dialogue_root, &asset_server, &mut commands, |p| {
+        node(dialogue_box, p, |p| match step {
+            DialogueStep::Line { speaker, .. } => {
+                if let Some(speaker) = speaker {
+                    text(speaker.clone(), (), dialogue_speaker_text, p);
+                }
+                text("", (), dialogue_text, p).set(&mut line);
+                node(dialogue_choices, p, |p| {
+                    let button = focus_text_button(
+                        "Continue",
+                        c_button.nb(),
+                        apply_button_state,
+                        button_text,
+                        p,
+                    );
+                    buttons.push((button, Buttons::Continue));
+                });
+            }
+            DialogueStep::Choices(choices) => {
+                node(dialogue_choices, p, |p| {
+                    for (index, label) in choices.iter() {
+                        let button = focus_text_button(
+                            label.clone(),
+                            c_button.nb(),
+                            apply_button_state,
+                            button_text,
+                            p,
+                        );
+                        buttons.push((button, Buttons::Choice(*index)));
+                    }
+                });
+            }
+        });
+    });
+    commands.entity(r).insert(DialogueBox);
+    if let (Some(line), DialogueStep::Line { text, .. }) = (line, step) {
+        commands.entity(line).insert(Typewriter {
+            text: text.clone(),
+            shown: 0.,
+        });
+    }
+    if let Some((first, _)) = buttons.first() {
+        nav.send(NavRequest::FocusOn(*first));
+    }
+    for (entity, button) in buttons {
+        commands.entity(entity).insert(button);
+    }
+}
+
+fn type_text(time: Res<Time>, mut lines: Query<(&mut Typewriter, &mut Text)>) {
+    for (mut typewriter, mut text) in lines.iter_mut() {
+        if !typewriter.finished() {
+            typewriter.shown += time.delta_seconds() * CHARACTERS_PER_SECOND;
+        }
+        let shown = typewriter
+            .text
+            .chars()
+            .take(typewriter.shown as usize)
+            .collect::<String>();
+        if text.sections[0].value != shown {
+            text.sections[0].value = shown;
+        }
+    }
+}
+
+fn process_input(
+    In(focused): In<Option<Entity>>,
+    interaction_query: TypedFocusedButtonQuery<'_, '_, '_, Buttons>,
+    mut lines: Query<&mut Typewriter>,
+    mut advance: EventWriter<AdvanceDialogue>,
+) {
+    let Some(focused) = focused else {
+        return;
+    };
+    let Some((_entity, btn)) = interaction_query.get(focused).ok() else {
+        return;
+    };
+    match btn {
+        Buttons::Continue => {
+            // The first press shows the rest of a line that's still being typed
+            let mut typing = false;
+            for mut typewriter in lines.iter_mut().filter(|t| !t.finished()) {
+                typewriter.shown = typewriter.text.chars().count() as f32;
+                typing = true;
+            }
+            if !typing {
+                advance.send(AdvanceDialogue(None));
+            }
+        }
+        Buttons::Choice(index) => advance.send(AdvanceDialogue(Some(*index))),
+    };
+}
+
+fn exit(mut commands: Commands, query: Query<Entity, With<DialogueBox>>) {
+    for item in query.iter() {
+        commands.entity(item).despawn_recursive();
+    }
+}
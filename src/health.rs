@@ -0,0 +1,415 @@
+//! Health, damage and death.
+//!
+//! Send a [`DamageEvent`] to hurt something, or give an entity a [`Damage`] component to hurt the
+//! entities it touches or that enter its [`Sensor`]. Hits grant a short [`Invulnerable`] window,
+//! [`Damaged`] and [`Died`] events report the results, and [`OnDeath`] decides what happens to the body.
+use bevy::prelude::*;
+
+use crate::{
+    in_game::{
+        game_state::GameState,
+        hud::{HudAppExt, HudValue},
+        InGameFixedUpdate, InGameUpdate,
+    },
+    juice::Flash,
+    levels::SpawnPoint,
+    physics::{Contacts, PhysicsSet, Sensor, TriggerEntered, Velocity},
+};
+
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>()
+            .add_event::<Damaged>()
+            .add_event::<Died>()
+            .register_type::<Health>()
+            .register_type::<Damage>()
+            .register_type::<Invulnerable>()
+            .register_type::<Team>()
+            .register_type::<OnDeath>()
+            .register_type::<FailOnDeath>()
+            .bind_hud_component::<Health>()
+            .add_systems(
+                InGameFixedUpdate,
+                (sensor_damage, contact_damage).after(PhysicsSet::Triggers),
+            )
+            .add_systems(
+                InGameUpdate,
+                (tick_invulnerability, apply_damage, handle_deaths, respawn).chain(),
+            );
+    }
+}
+
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+    /// How long the entity is [`Invulnerable`] after being hit, in seconds
+    pub invulnerability: f32,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new(3.)
+    }
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            invulnerability: 0.5,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_invulnerability(mut self, seconds: f32) -> Self {
+        self.invulnerability = seconds;
+        self
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.
+    }
+
+    #[allow(dead_code)]
+    pub fn heal(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
+    /// For showing health in the HUD, with a [`crate::in_game::hud::HudComponentBinding`].
+    #[allow(dead_code)]
+    pub fn hud_value(&self) -> HudValue {
+        HudValue::new(self.current, self.max)
+    }
+}
+
+/// Hurts the entities this one touches, or that enter it if it's a [`Sensor`].
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct Damage {
+    pub amount: f32,
+    /// Removes this entity after it hurts something - for projectiles
+    pub despawn_on_hit: bool,
+}
+
+impl Default for Damage {
+    fn default() -> Self {
+        Self {
+            amount: 1.,
+            despawn_on_hit: false,
+        }
+    }
+}
+
+/// Ignores damage until the time runs out.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct Invulnerable {
+    /// In seconds - infinite keeps it invulnerable until the component is removed
+    pub remaining: f32,
+}
+
+impl Invulnerable {
+    pub fn for_seconds(seconds: f32) -> Self {
+        Self { remaining: seconds }
+    }
+
+    #[allow(dead_code)]
+    pub fn forever() -> Self {
+        Self {
+            remaining: f32::INFINITY,
+        }
+    }
+}
+
+/// Entities can't hurt others on the same team, except for neutral ones like hazards which hurt everyone.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub enum Team {
+    Player,
+    Enemy,
+    #[default]
+    Neutral,
+}
+
+impl Team {
+    pub fn can_hurt(&self, other: &Team) -> bool {
+        self != other || *self == Team::Neutral
+    }
+}
+
+/// What happens to an entity when its health runs out. Entities without one are left as they are.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub enum OnDeath {
+    #[default]
+    Despawn,
+    /// Hides the entity, then brings it back with full health after `delay` seconds - at the
+    /// [`SpawnPoint`] with the given name, or wherever it was when it died
+    Respawn {
+        delay: f32,
+        spawn_point: Option<String>,
+    },
+}
+
+/// Sends the game to [`GameState::Failed`] when this entity dies - put it on the player.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct FailOnDeath;
+
+/// Asks for an entity to be hurt. Ignored while it's [`Invulnerable`] or dead.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: f32,
+    pub source: Option<Entity>,
+}
+
+/// Sent after an entity has lost health.
+#[derive(Event, Clone, Copy, Debug)]
+#[allow(dead_code)]
+pub struct Damaged {
+    pub entity: Entity,
+    pub amount: f32,
+    pub source: Option<Entity>,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Died {
+    pub entity: Entity,
+    #[allow(dead_code)]
+    pub source: Option<Entity>,
+}
+
+/// Added while an entity waits to come back after [`OnDeath::Respawn`]. Physics, damage and the
+/// controllers leave it alone until then.
+#[derive(Component)]
+pub struct Respawning {
+    remaining: f32,
+    spawn_point: Option<String>,
+}
+
+fn hit(
+    source: Entity,
+    damage: &Damage,
+    source_team: Option<&Team>,
+    target: Entity,
+    target_team: Option<&Team>,
+    events: &mut EventWriter<DamageEvent>,
+    commands: &mut Commands,
+) {
+    let source_team = source_team.copied().unwrap_or_default();
+    let target_team = target_team.copied().unwrap_or_default();
+    if source == target || !source_team.can_hurt(&target_team) {
+        return;
+    }
+    events.send(DamageEvent {
+        target,
+        amount: damage.amount,
+        source: Some(source),
+    });
+    if damage.despawn_on_hit {
+        commands.entity(source).despawn_recursive();
+    }
+}
+
+type DamageSourceQuery<'w, 's, 'a, F> =
+    Query<'w, 's, (&'a Damage, Option<&'a Team>), (F, Without<Respawning>)>;
+
+type VulnerableQuery<'w, 's, 'a> =
+    Query<'w, 's, Option<&'a Team>, (With<Health>, Without<Invulnerable>, Without<Respawning>)>;
+
+type RespawningQuery<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'a mut Respawning,
+        &'a mut Health,
+        &'a mut Transform,
+        Option<&'a Parent>,
+        Option<&'a mut Velocity>,
+    ),
+>;
+
+fn sensor_damage(
+    mut commands: Commands,
+    mut triggers: EventReader<TriggerEntered>,
+    sources: DamageSourceQuery<With<Sensor>>,
+    targets: Query<Option<&Team>, (With<Health>, Without<Respawning>)>,
+    mut events: EventWriter<DamageEvent>,
+) {
+    for trigger in triggers.read() {
+        let (Ok((damage, source_team)), Ok(target_team)) =
+            (sources.get(trigger.sensor), targets.get(trigger.entity))
+        else {
+            continue;
+        };
+        hit(
+            trigger.sensor,
+            damage,
+            source_team,
+            trigger.entity,
+            target_team,
+            &mut events,
+            &mut commands,
+        );
+    }
+}
+
+/// Solid colliders with [`Damage`] hurt what they touch, whichever of the two was moving.
+fn contact_damage(
+    mut commands: Commands,
+    bodies: Query<(Entity, &Contacts), Without<Respawning>>,
+    sources: DamageSourceQuery<Without<Sensor>>,
+    targets: VulnerableQuery,
+    mut events: EventWriter<DamageEvent>,
+) {
+    for (entity, contacts) in bodies.iter() {
+        for contact in contacts.contacts.iter() {
+            for (source, target) in [(contact.entity, entity), (entity, contact.entity)] {
+                let (Ok((damage, source_team)), Ok(target_team)) =
+                    (sources.get(source), targets.get(target))
+                else {
+                    continue;
+                };
+                hit(
+                    source,
+                    damage,
+                    source_team,
+                    target,
+                    target_team,
+                    &mut events,
+                    &mut commands,
+                );
+            }
+        }
+    }
+}
+
+fn tick_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut invulnerable: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut invulnerable) in invulnerable.iter_mut() {
+        invulnerable.remaining -= time.delta_seconds();
+        if invulnerable.remaining <= 0. {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+fn apply_damage(
+    mut commands: Commands,
+    mut events: EventReader<DamageEvent>,
+    mut targets: Query<(&mut Health, Has<Invulnerable>), Without<Respawning>>,
+    mut damaged: EventWriter<Damaged>,
+    mut died: EventWriter<Died>,
+    mut flashes: EventWriter<Flash>,
+) {
+    let mut hit = vec![];
+    for event in events.read() {
+        // Only the first hit in a frame counts, as it makes the entity invulnerable
+        if hit.contains(&event.target) {
+            continue;
+        }
+        let Ok((mut health, invulnerable)) = targets.get_mut(event.target) else {
+            continue;
+        };
+        if invulnerable || health.is_dead() || event.amount <= 0. {
+            continue;
+        }
+        hit.push(event.target);
+
+        health.current = (health.current - event.amount).max(0.);
+        damaged.send(Damaged {
+            entity: event.target,
+            amount: event.amount,
+            source: event.source,
+        });
+        flashes.send(Flash::new(event.target));
+
+        if health.is_dead() {
+            died.send(Died {
+                entity: event.target,
+                source: event.source,
+            });
+        } else if health.invulnerability > 0. {
+            commands
+                .entity(event.target)
+                .insert(Invulnerable::for_seconds(health.invulnerability));
+        }
+    }
+}
+
+fn handle_deaths(
+    mut commands: Commands,
+    mut died: EventReader<Died>,
+    entities: Query<(Option<&OnDeath>, Has<FailOnDeath>)>,
+) {
+    for event in died.read() {
+        let Ok((on_death, fail)) = entities.get(event.entity) else {
+            continue;
+        };
+        if fail {
+            commands.insert_resource(NextState(Some(GameState::Failed)));
+        }
+        match on_death {
+            Some(OnDeath::Despawn) => commands.entity(event.entity).despawn_recursive(),
+            Some(OnDeath::Respawn { delay, spawn_point }) => {
+                commands.entity(event.entity).insert((
+                    Respawning {
+                        remaining: *delay,
+                        spawn_point: spawn_point.clone(),
+                    },
+                    Visibility::Hidden,
+                ));
+            }
+            None => {}
+        }
+    }
+}
+
+fn respawn(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut respawning: RespawningQuery,
+    spawn_points: Query<(&SpawnPoint, &GlobalTransform)>,
+    globals: Query<&GlobalTransform>,
+) {
+    for (entity, mut respawning, mut health, mut transform, parent, velocity) in
+        respawning.iter_mut()
+    {
+        respawning.remaining -= time.delta_seconds();
+        if respawning.remaining > 0. {
+            continue;
+        }
+
+        let spawn_point = respawning.spawn_point.as_ref().and_then(|name| {
+            spawn_points
+                .iter()
+                .find(|(point, _)| &point.0 == name)
+                .map(|(_, global)| global.translation())
+        });
+        if let Some(position) = spawn_point {
+            let parent = parent
+                .and_then(|parent| globals.get(parent.get()).ok())
+                .map(|global| global.translation())
+                .unwrap_or_default();
+            transform.translation = position - parent;
+        }
+        if let Some(mut velocity) = velocity {
+            velocity.0 = Vec2::ZERO;
+        }
+        health.current = health.max;
+        commands.entity(entity).remove::<Respawning>().insert((
+            Visibility::Inherited,
+            Invulnerable::for_seconds(health.invulnerability),
+        ));
+    }
+}
@@ -4,6 +4,8 @@ use bevy_ui_dsl::*;
 
 use crate::{
     app_state::AppState,
+    levels::{CurrentLevel, RestartLevel},
+    score::{high_score_table, name_entry, score_summary, HighScores, RunTimer, Score},
     ui::{
         buttons::{focus_text_button, focused_button_activated, TypedFocusedButtonQuery},
         classes::*,
@@ -24,43 +26,70 @@ impl Plugin for GameOverPlugin {
 }
 #[dexterous_developer_setup(game_over)]
 fn reloadable(app: &mut ReloadableAppContents) {
-    app.reset_setup_in_state::<Screen, _, _>(GameState::Complete, setup)
+    app.reset_setup_in_state::<Screen, _, _>(GameState::Failed, setup)
         .add_systems(
             Update,
             (
                 process_keyboard_input,
                 (focused_button_activated.pipe(process_input)),
             )
-                .run_if(in_state(GameState::Complete)),
+                .run_if(in_state(GameState::Failed)),
         );
 }
 
 #[derive(Component)]
 struct Screen;
 
-#[derive(Component)]
-struct Button;
+#[derive(Component, Clone, Copy)]
+enum Buttons {
+    Restart,
+    Menu,
+}
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let mut menu_button = None;
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    score: Res<Score>,
+    timer: Res<RunTimer>,
+    high_scores: Res<HighScores>,
+    current: Res<CurrentLevel>,
+) {
+    let best = high_scores.best(&current.path);
+    let qualifies = high_scores.qualifies(&current.path, score.0);
+    let mut buttons = vec![];
     let r = root((overlay, c_root), &asset_server, &mut commands, |p| {
         node(primary_box, p, |p| {
             node((span.nb(), primary_box_main.nb()), p, |p| {
                 text("Game", (), main_text, p);
                 text("Over", (), main_text, p);
             });
-            focus_text_button(
+            score_summary(*score, *timer, best, p);
+            if qualifies {
+                name_entry(current.path.clone(), *score, *timer, p);
+            }
+            high_score_table(current.path.clone(), &high_scores, p);
+            let button = focus_text_button(
+                "Restart",
+                (c_button.nb(), primary_box_item.nb()),
+                apply_button_state,
+                button_text,
+                p,
+            );
+            buttons.push((button, Buttons::Restart));
+            let button = focus_text_button(
                 "Main Menu",
                 (c_button.nb(), primary_box_item.nb()),
                 apply_button_state,
                 button_text,
                 p,
-            )
-            .set(&mut menu_button);
+            );
+            buttons.push((button, Buttons::Menu));
         });
     });
     commands.entity(r).insert(Screen);
-    commands.entity(menu_button.unwrap()).insert(Button);
+    for (entity, button) in buttons {
+        commands.entity(entity).insert(button);
+    }
 }
 
 fn exit(mut commands: Commands, query: Query<Entity, With<Screen>>) {
@@ -72,15 +101,18 @@ fn exit(mut commands: Commands, query: Query<Entity, With<Screen>>) {
 fn process_input(
     In(focused): In<Option<Entity>>,
     mut commands: Commands,
-    interaction_query: TypedFocusedButtonQuery<'_, '_, '_, Button>,
+    interaction_query: TypedFocusedButtonQuery<'_, '_, '_, Buttons>,
 ) {
     let Some(focused) = focused else {
         return;
     };
-    let Some((_entity, _btn)) = interaction_query.get(focused).ok() else {
+    let Some((_entity, btn)) = interaction_query.get(focused).ok() else {
         return;
     };
-    commands.insert_resource(NextState(Some(AppState::MainMenu)));
+    match btn {
+        Buttons::Restart => commands.add(RestartLevel),
+        Buttons::Menu => commands.insert_resource(NextState(Some(AppState::MainMenu))),
+    };
 }
 
 fn process_keyboard_input(mut commands: Commands, keys: Res<Input<KeyCode>>) {
@@ -6,6 +6,8 @@ use bevy_inspector_egui::{prelude::ReflectInspectorOptions, InspectorOptions};
 pub enum GameState {
     #[default]
     None,
+    /// Waiting for the current level's assets before spawning it
+    LoadingLevel,
     InGame,
     Failed,
     Complete,
@@ -17,4 +19,6 @@ pub enum PauseState {
     #[default]
     None,
     Paused,
+    /// A conversation is on screen - gameplay is suspended as when paused, without the pause menu
+    Dialogue,
 }
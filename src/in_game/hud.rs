@@ -0,0 +1,422 @@
+use bevy::prelude::*;
+use bevy_ui_dsl::*;
+use bevy_vector_shapes::prelude::*;
+
+use crate::{
+    app_state::AppState,
+    assets::MainGameAssets,
+    camera::MainCamera,
+    ui::{classes::*, colors, DisplayBundle},
+};
+
+use super::game_state::PauseState;
+use dexterous_developer::{
+    dexterous_developer_setup, ReloadableApp, ReloadableAppContents, ReloadableElementsSetup,
+};
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HudValue>()
+            .register_type::<WorldBar>()
+            .setup_reloadable_elements::<reloadable>();
+    }
+}
+
+#[dexterous_developer_setup(hud)]
+fn reloadable(app: &mut ReloadableAppContents) {
+    app.reset_setup_in_state::<Hud, _, _>(AppState::InGame, setup)
+        .add_systems(OnEnter(PauseState::Paused), hide_hud)
+        .add_systems(OnExit(PauseState::Paused), show_hud)
+        .add_systems(
+            Update,
+            (
+                (
+                    update_bars,
+                    update_counters,
+                    update_timers,
+                    update_icon_rows,
+                ),
+                draw_world_bars.run_if(in_state(PauseState::None)),
+            )
+                .run_if(in_state(AppState::InGame)),
+        );
+}
+
+/// Extends the app with the bindings a HUD widget can be driven by.
+///
+/// Each component or resource type that feeds the HUD needs to be registered once,
+/// after which any widget can carry a [`HudComponentBinding`] or [`HudResourceBinding`] for it.
+pub trait HudAppExt {
+    fn bind_hud_component<C: Component>(&mut self) -> &mut Self;
+    fn bind_hud_resource<R: Resource>(&mut self) -> &mut Self;
+}
+
+impl HudAppExt for App {
+    fn bind_hud_component<C: Component>(&mut self) -> &mut Self {
+        self.add_systems(
+            Update,
+            update_component_bindings::<C>
+                .before(update_bars)
+                .before(update_counters)
+                .before(update_timers)
+                .before(update_icon_rows)
+                .run_if(in_state(AppState::InGame)),
+        )
+    }
+
+    fn bind_hud_resource<R: Resource>(&mut self) -> &mut Self {
+        self.add_systems(
+            Update,
+            update_resource_bindings::<R>
+                .before(update_bars)
+                .before(update_counters)
+                .before(update_timers)
+                .before(update_icon_rows)
+                .run_if(in_state(AppState::InGame)),
+        )
+    }
+}
+
+/// The value displayed by a HUD widget. Bars display `current / max`,
+/// counters and timers display `current`, and icon rows show `current` icons.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct HudValue {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl HudValue {
+    pub fn new(current: f32, max: f32) -> Self {
+        Self { current, max }
+    }
+
+    pub fn ratio(&self) -> f32 {
+        if self.max <= 0. {
+            0.
+        } else {
+            (self.current / self.max).clamp(0., 1.)
+        }
+    }
+}
+
+/// Reads a [`HudValue`] from a component on another entity.
+#[derive(Component)]
+pub struct HudComponentBinding<C: Component> {
+    pub source: Entity,
+    pub read: fn(&C) -> HudValue,
+}
+
+impl<C: Component> HudComponentBinding<C> {
+    #[allow(dead_code)]
+    pub fn new(source: Entity, read: fn(&C) -> HudValue) -> Self {
+        Self { source, read }
+    }
+}
+
+/// Reads a [`HudValue`] from a resource.
+#[derive(Component)]
+pub struct HudResourceBinding<R: Resource> {
+    pub read: fn(&R) -> HudValue,
+}
+
+impl<R: Resource> HudResourceBinding<R> {
+    #[allow(dead_code)]
+    pub fn new(read: fn(&R) -> HudValue) -> Self {
+        Self { read }
+    }
+}
+
+/// A bar drawn in world space above the entity it is attached to, in world units - pixels in 2D.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct WorldBar {
+    pub offset: Vec3,
+    pub size: Vec2,
+    pub color: Color,
+    pub background: Color,
+}
+
+impl Default for WorldBar {
+    fn default() -> Self {
+        Self {
+            offset: Vec3::Y * 1.5,
+            size: Vec2::new(1., 0.15),
+            color: colors::HUD_BAR_FILL_COLOR,
+            background: colors::HUD_BAR_BACKGROUND_COLOR,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Hud;
+
+/// The top left corner of the HUD, where widgets tracking the player go.
+#[derive(Component)]
+pub struct HudPrimary;
+
+/// The top right corner of the HUD, where widgets tracking the run go.
+#[derive(Component)]
+pub struct HudSecondary;
+
+#[derive(Component)]
+pub struct HudBar;
+
+#[derive(Component)]
+struct HudBarFill;
+
+#[derive(Component)]
+pub struct HudCounter(pub String);
+
+#[derive(Component)]
+pub struct HudTimer(pub String);
+
+#[derive(Component)]
+pub struct HudIconRow;
+
+#[derive(Component)]
+struct HudIcon(usize);
+
+/// Spawns a screen space bar, filled according to its [`HudValue`].
+#[allow(dead_code)]
+pub fn hud_screen_bar(
+    class: impl AssetClass<NodeBundle>,
+    value: HudValue,
+    parent: &mut UiChildBuilder,
+) -> Entity {
+    let mut bundle = NodeBundle::default();
+    hud_bar(&mut bundle);
+    class.apply(parent.assets(), &mut bundle);
+    parent
+        .spawn((bundle, HudBar, value))
+        .with_children(|p| {
+            let mut fill = NodeBundle::default();
+            hud_bar_fill(&mut fill);
+            p.spawn((fill, HudBarFill));
+        })
+        .id()
+}
+
+/// Spawns a numeric counter, displayed after the `label`.
+#[allow(dead_code)]
+pub fn hud_counter(
+    label: impl Into<String>,
+    value: HudValue,
+    parent: &mut UiChildBuilder,
+) -> Entity {
+    let label = label.into();
+    let txt = format_counter(&label, &value);
+    parent
+        .spawn((NodeBundle::default(), HudCounter(label), value))
+        .with_children(|p| {
+            text(txt, (), hud_text, p);
+        })
+        .id()
+}
+
+/// Spawns a timer, displaying the value in seconds as minutes & seconds after the `label`.
+#[allow(dead_code)]
+pub fn hud_timer(label: impl Into<String>, value: HudValue, parent: &mut UiChildBuilder) -> Entity {
+    let label = label.into();
+    let txt = format_timer(&label, &value);
+    parent
+        .spawn((NodeBundle::default(), HudTimer(label), value))
+        .with_children(|p| {
+            text(txt, (), hud_text, p);
+        })
+        .id()
+}
+
+/// Spawns a row of `value.max` icons, showing only the first `value.current` of them.
+#[allow(dead_code)]
+pub fn hud_icons(
+    icon: &dyn DisplayBundle,
+    icon_size: f32,
+    value: HudValue,
+    assets: &MainGameAssets,
+    parent: &mut UiChildBuilder,
+) -> Entity {
+    let mut bundle = NodeBundle::default();
+    hud_icon_row(&mut bundle);
+    parent
+        .spawn((bundle, HudIconRow, value))
+        .with_children(|p| {
+            for i in 0..(value.max.max(0.) as usize) {
+                p.spawn((
+                    NodeBundle {
+                        visibility: icon_visibility(i, &value),
+                        ..default()
+                    },
+                    HudIcon(i),
+                ))
+                .with_children(|p| icon.display_bundle(assets, icon_size, p));
+            }
+        })
+        .id()
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let mut primary = None;
+    let mut secondary = None;
+    let r = root(hud_root, &asset_server, &mut commands, |p| {
+        node(hud_row, p, |p| {
+            node(hud_group, p, |_| {}).set(&mut primary);
+            node(hud_group, p, |_| {}).set(&mut secondary);
+        });
+    });
+    commands.entity(r).insert(Hud);
+    commands.entity(primary.unwrap()).insert(HudPrimary);
+    commands.entity(secondary.unwrap()).insert(HudSecondary);
+}
+
+fn hide_hud(mut query: Query<&mut Visibility, With<Hud>>) {
+    for mut visibility in query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn show_hud(mut query: Query<&mut Visibility, With<Hud>>) {
+    for mut visibility in query.iter_mut() {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn update_component_bindings<C: Component>(
+    mut widgets: Query<(&HudComponentBinding<C>, &mut HudValue)>,
+    sources: Query<&C>,
+) {
+    for (binding, mut value) in widgets.iter_mut() {
+        let Ok(source) = sources.get(binding.source) else {
+            continue;
+        };
+        let next = (binding.read)(source);
+        if *value != next {
+            *value = next;
+        }
+    }
+}
+
+fn update_resource_bindings<R: Resource>(
+    mut widgets: Query<(&HudResourceBinding<R>, &mut HudValue)>,
+    source: Option<Res<R>>,
+) {
+    let Some(source) = source else {
+        return;
+    };
+    for (binding, mut value) in widgets.iter_mut() {
+        let next = (binding.read)(&source);
+        if *value != next {
+            *value = next;
+        }
+    }
+}
+
+type ChangedWidgetQuery<'w, 's, 'a, W> =
+    Query<'w, 's, (&'a HudValue, &'a Children), (With<W>, Changed<HudValue>)>;
+
+fn update_bars(bars: ChangedWidgetQuery<HudBar>, mut fills: Query<&mut Style, With<HudBarFill>>) {
+    for (value, children) in bars.iter() {
+        for child in children.iter() {
+            if let Ok(mut style) = fills.get_mut(*child) {
+                style.width = Val::Percent(value.ratio() * 100.);
+            }
+        }
+    }
+}
+
+fn update_counters(
+    counters: Query<(&HudValue, &HudCounter, &Children), Changed<HudValue>>,
+    mut texts: Query<&mut Text>,
+) {
+    for (value, counter, children) in counters.iter() {
+        set_child_text(children, &mut texts, format_counter(&counter.0, value));
+    }
+}
+
+fn update_timers(
+    timers: Query<(&HudValue, &HudTimer, &Children), Changed<HudValue>>,
+    mut texts: Query<&mut Text>,
+) {
+    for (value, timer, children) in timers.iter() {
+        set_child_text(children, &mut texts, format_timer(&timer.0, value));
+    }
+}
+
+fn update_icon_rows(
+    rows: ChangedWidgetQuery<HudIconRow>,
+    mut icons: Query<(&HudIcon, &mut Visibility)>,
+) {
+    for (value, children) in rows.iter() {
+        for child in children.iter() {
+            if let Ok((icon, mut visibility)) = icons.get_mut(*child) {
+                *visibility = icon_visibility(icon.0, value);
+            }
+        }
+    }
+}
+
+fn draw_world_bars(
+    mut painter: ShapePainter,
+    bars: Query<(&WorldBar, &HudValue, &GlobalTransform)>,
+    camera: Query<(&GlobalTransform, Has<Camera3d>), With<MainCamera>>,
+) {
+    let Some((camera, is_3d)) = camera.iter().next() else {
+        return;
+    };
+    let rotation = camera.compute_transform().rotation;
+
+    if is_3d {
+        painter.set_3d();
+    } else {
+        painter.set_2d();
+    }
+    painter.hollow = false;
+
+    for (bar, value, transform) in bars.iter() {
+        let position = transform.translation() + bar.offset;
+
+        painter.transform = Transform::from_translation(position).with_rotation(rotation);
+        painter.color = bar.background;
+        painter.rect(bar.size);
+
+        let fill = bar.size.x * value.ratio();
+        if fill <= 0. {
+            continue;
+        }
+        let shift = rotation * Vec3::new((fill - bar.size.x) / 2., 0., 0.001);
+        painter.transform = Transform::from_translation(position + shift).with_rotation(rotation);
+        painter.color = bar.color;
+        painter.rect(Vec2::new(fill, bar.size.y));
+    }
+}
+
+fn set_child_text(children: &Children, texts: &mut Query<&mut Text>, value: String) {
+    for child in children.iter() {
+        if let Ok(mut text) = texts.get_mut(*child) {
+            if let Some(section) = text.sections.first_mut() {
+                section.value = value.clone();
+            }
+        }
+    }
+}
+
+fn icon_visibility(index: usize, value: &HudValue) -> Visibility {
+    if (index as f32) < value.current {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn format_counter(label: &str, value: &HudValue) -> String {
+    format!("{label}{}", value.current.round() as i64)
+}
+
+fn format_timer(label: &str, value: &HudValue) -> String {
+    let seconds = value.current.max(0.);
+    let minutes = (seconds / 60.).floor() as u32;
+    let seconds = seconds % 60.;
+    format!("{label}{minutes:02}:{seconds:05.2}")
+}
@@ -1,6 +1,7 @@
 mod game_completed;
 mod game_over;
-mod game_state;
+pub mod game_state;
+pub mod hud;
 mod pause_screen;
 
 use bevy::{
@@ -11,16 +12,13 @@ use bevy::{
 };
 use bevy_inspector_egui::quick::StateInspectorPlugin;
 
-use crate::{
-    app_state::AppState,
-    assets::MainGameAssets,
-    ui::colors::{DEFAULT_AMBIENT, DEFAULT_CLEAR},
-};
+use crate::{app_state::AppState, assets::MainGameAssets, ui::colors::DEFAULT_CLEAR};
 
 use self::{
     game_completed::GameCompletedPlugin,
     game_over::GameOverPlugin,
     game_state::{GameState, PauseState},
+    hud::HudPlugin,
     pause_screen::PausePlugin,
 };
 use dexterous_developer::{
@@ -30,9 +28,10 @@ pub struct InGamePlugin;
 
 impl Plugin for InGamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((PausePlugin, GameOverPlugin, GameCompletedPlugin))
+        app.add_plugins((PausePlugin, GameOverPlugin, GameCompletedPlugin, HudPlugin))
             .add_state::<GameState>()
             .register_type::<GameState>()
+            .register_type::<InGame>()
             .add_plugins(
                 StateInspectorPlugin::<GameState>::default()
                     .run_if(input_toggle_active(false, KeyCode::F1)),
@@ -49,18 +48,27 @@ fn reloadable(app: &mut ReloadableAppContents) {
         .add_systems(
             Update,
             run_in_game_update.run_if(in_state(PauseState::None)),
+        )
+        .add_systems(
+            FixedUpdate,
+            run_in_game_fixed_update.run_if(in_state(PauseState::None)),
         );
 }
 
-#[derive(Component)]
-struct InGame;
+/// The root of all entities spawned while in game.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct InGame;
 
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct InGameUpdate;
 
+/// Runs at the fixed timestep while the game isn't paused - for anything that needs to be deterministic.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InGameFixedUpdate;
+
 fn setup(mut commands: Commands, assets: Res<MainGameAssets>) {
     commands.insert_resource(ClearColor(DEFAULT_CLEAR));
-    commands.insert_resource(DEFAULT_AMBIENT);
     commands
         .spawn((
             InGame,
@@ -104,3 +112,7 @@ fn enable_audio(audio: Query<&AudioSink>) {
 fn run_in_game_update(world: &mut World) {
     let _ = world.try_run_schedule(InGameUpdate);
 }
+
+fn run_in_game_fixed_update(world: &mut World) {
+    let _ = world.try_run_schedule(InGameFixedUpdate);
+}
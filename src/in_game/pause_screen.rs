@@ -4,6 +4,8 @@ use bevy_ui_dsl::*;
 
 use crate::{
     app_state::AppState,
+    menus::quit::{quit_button, QuitButton, QuitState},
+    save::{ActiveSaveSlot, SaveGame},
     ui::{
         buttons::{focus_text_button, focused_button_activated, TypedFocusedButtonQuery},
         classes::*,
@@ -28,7 +30,7 @@ impl Plugin for PausePlugin {
             .add_systems(
                 Update,
                 (
-                    process_keyboard_input,
+                    process_keyboard_input.run_if(in_state(QuitState::None)),
                     (focused_button_activated.pipe(process_input)),
                 )
                     .run_if(in_state(AppState::InGame)),
@@ -41,7 +43,7 @@ fn reloadable(app: &mut ReloadableAppContents) {
         .add_systems(
             Update,
             (
-                process_keyboard_input,
+                process_keyboard_input.run_if(in_state(QuitState::None)),
                 (focused_button_activated.pipe(process_input)),
             )
                 .run_if(in_state(AppState::InGame)),
@@ -54,12 +56,15 @@ struct Screen;
 #[derive(Component)]
 enum Buttons {
     Resume,
+    Save,
     Menu,
 }
 
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     let mut resume_button = None;
+    let mut save_button = None;
     let mut menu_button = None;
+    let mut quit = None;
     let r = root((overlay, c_root), &asset_server, &mut commands, |p| {
         node(primary_box, p, |p| {
             node((span.nb(), primary_box_main.nb()), p, |p| {
@@ -74,6 +79,14 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 p,
             )
             .set(&mut resume_button);
+            focus_text_button(
+                "Save Game",
+                (c_button.nb(), primary_box_item.nb()),
+                apply_button_state,
+                button_text,
+                p,
+            )
+            .set(&mut save_button);
             focus_text_button(
                 "Main Menu",
                 (c_button.nb(), primary_box_item.nb()),
@@ -82,13 +95,18 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 p,
             )
             .set(&mut menu_button);
+            quit = quit_button(p);
         });
     });
     commands.entity(r).insert(Screen);
     commands
         .entity(resume_button.unwrap())
         .insert(Buttons::Resume);
+    commands.entity(save_button.unwrap()).insert(Buttons::Save);
     commands.entity(menu_button.unwrap()).insert(Buttons::Menu);
+    if let Some(quit) = quit {
+        commands.entity(quit).insert(QuitButton);
+    }
 }
 
 fn exit(mut commands: Commands, query: Query<Entity, With<Screen>>) {
@@ -102,6 +120,7 @@ fn process_input(
     mut commands: Commands,
     interaction_query: TypedFocusedButtonQuery<'_, '_, '_, Buttons>,
     paused: Res<State<PauseState>>,
+    slot: Res<ActiveSaveSlot>,
 ) {
     let Some(focused) = focused else {
         return;
@@ -110,10 +129,12 @@ fn process_input(
         return;
     };
     match btn {
-        Buttons::Resume => commands.insert_resource(NextState(Some(match paused.get() {
-            PauseState::None => PauseState::Paused,
-            PauseState::Paused => PauseState::None,
-        }))),
+        Buttons::Resume => match paused.get() {
+            PauseState::None => commands.insert_resource(NextState(Some(PauseState::Paused))),
+            PauseState::Paused => commands.insert_resource(NextState(Some(PauseState::None))),
+            PauseState::Dialogue => {}
+        },
+        Buttons::Save => commands.add(SaveGame { slot: slot.0 }),
         Buttons::Menu => commands.insert_resource(NextState(Some(AppState::MainMenu))),
     };
 }
@@ -124,9 +145,11 @@ fn process_keyboard_input(
     paused: Res<State<PauseState>>,
 ) {
     if keys.just_pressed(KeyCode::Escape) {
-        commands.insert_resource(NextState(Some(match paused.get() {
-            PauseState::None => PauseState::Paused,
-            PauseState::Paused => PauseState::None,
-        })));
+        match paused.get() {
+            PauseState::None => commands.insert_resource(NextState(Some(PauseState::Paused))),
+            PauseState::Paused => commands.insert_resource(NextState(Some(PauseState::None))),
+            // Conversations can't be paused, as resuming would leave them behind
+            PauseState::Dialogue => {}
+        }
     }
 }
@@ -0,0 +1,218 @@
+//! Game feel effects - screen shake, hit-stop and flashes - triggered through events,
+//! scaled by the effect intensity setting and disabled entirely by reduced motion.
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    app_state::AppState, camera::CameraShake, in_game::game_state::PauseState, settings::Settings,
+    toon_material::ToonMaterial,
+};
+
+pub struct JuicePlugin;
+
+impl Plugin for JuicePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ScreenShake>()
+            .add_event::<HitStop>()
+            .add_event::<Flash>()
+            .init_resource::<HitStopTimer>()
+            .add_systems(
+                Update,
+                (
+                    apply_screen_shake,
+                    start_hit_stop,
+                    update_hit_stop.run_if(in_state(PauseState::None)),
+                    start_flash::<StandardMaterial>,
+                    start_flash::<ToonMaterial>,
+                    update_flash::<StandardMaterial>,
+                    update_flash::<ToonMaterial>,
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(OnExit(AppState::InGame), end_hit_stop);
+    }
+}
+
+/// Adds trauma to the in game camera's [`CameraShake`].
+#[derive(Event, Clone, Copy, Debug)]
+#[allow(dead_code)]
+pub struct ScreenShake {
+    pub trauma: f32,
+}
+
+/// Briefly freezes virtual time, to give impacts more weight.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct HitStop {
+    pub duration: Duration,
+}
+
+impl HitStop {
+    #[allow(dead_code)]
+    pub fn from_secs(seconds: f32) -> Self {
+        Self {
+            duration: Duration::from_secs_f32(seconds),
+        }
+    }
+}
+
+/// Tints the materials of an entity, and all its descendants, for a moment.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Flash {
+    pub entity: Entity,
+    pub color: Color,
+    pub duration: Duration,
+}
+
+impl Flash {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            color: Color::WHITE,
+            duration: Duration::from_secs_f32(0.1),
+        }
+    }
+}
+
+/// A material that can be tinted by a [`Flash`].
+pub trait FlashMaterial: Material {
+    fn flashed(&self, color: Color, amount: f32) -> Self;
+}
+
+impl FlashMaterial for StandardMaterial {
+    fn flashed(&self, color: Color, amount: f32) -> Self {
+        let mut material = self.clone();
+        material.base_color = lerp_color(self.base_color, color, amount);
+        material.emissive = lerp_color(self.emissive, color, amount);
+        material
+    }
+}
+
+impl FlashMaterial for ToonMaterial {
+    fn flashed(&self, color: Color, amount: f32) -> Self {
+        let mut material = self.clone();
+        material.base_color = lerp_color(self.base_color, color, amount);
+        material
+    }
+}
+
+fn lerp_color(from: Color, to: Color, amount: f32) -> Color {
+    let from = Vec4::from(from.as_rgba_f32());
+    let to = Vec4::from(to.as_rgba_f32());
+    let [r, g, b, a] = from.lerp(to, amount).to_array();
+    Color::rgba(r, g, b, a)
+}
+
+#[derive(Resource, Default)]
+struct HitStopTimer(Option<Timer>);
+
+#[derive(Component)]
+struct Flashing<M: Material> {
+    original: Handle<M>,
+    timer: Timer,
+}
+
+fn apply_screen_shake(
+    mut events: EventReader<ScreenShake>,
+    settings: Res<Settings>,
+    mut cameras: Query<&mut CameraShake>,
+) {
+    let trauma: f32 = events.read().map(|e| e.trauma).sum();
+    let trauma = trauma * settings.effect_scale();
+    if trauma <= 0. {
+        return;
+    }
+    for mut shake in cameras.iter_mut() {
+        shake.add_trauma(trauma);
+    }
+}
+
+fn start_hit_stop(
+    mut events: EventReader<HitStop>,
+    settings: Res<Settings>,
+    mut timer: ResMut<HitStopTimer>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let Some(duration) = events.read().map(|e| e.duration).max() else {
+        return;
+    };
+    let duration = duration.mul_f32(settings.effect_scale());
+    if duration.is_zero() {
+        return;
+    }
+    let remaining = timer.0.as_ref().map(|t| t.remaining()).unwrap_or_default();
+    if duration > remaining {
+        timer.0 = Some(Timer::new(duration, TimerMode::Once));
+    }
+    time.pause();
+}
+
+fn update_hit_stop(
+    real: Res<Time<Real>>,
+    mut timer: ResMut<HitStopTimer>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let Some(stop) = timer.0.as_mut() else {
+        return;
+    };
+    if stop.tick(real.delta()).finished() {
+        timer.0 = None;
+        time.unpause();
+    }
+}
+
+fn end_hit_stop(mut timer: ResMut<HitStopTimer>, mut time: ResMut<Time<Virtual>>) {
+    if timer.0.take().is_some() {
+        time.unpause();
+    }
+}
+
+fn start_flash<M: FlashMaterial>(
+    mut commands: Commands,
+    mut events: EventReader<Flash>,
+    settings: Res<Settings>,
+    children: Query<&Children>,
+    mut targets: Query<(&mut Handle<M>, Option<&mut Flashing<M>>)>,
+    mut materials: ResMut<Assets<M>>,
+) {
+    let scale = settings.effect_scale();
+    for flash in events.read() {
+        if scale <= 0. {
+            continue;
+        }
+        for entity in std::iter::once(flash.entity).chain(children.iter_descendants(flash.entity)) {
+            let Ok((mut handle, flashing)) = targets.get_mut(entity) else {
+                continue;
+            };
+            if let Some(mut flashing) = flashing {
+                flashing.timer = Timer::new(flash.duration, TimerMode::Once);
+                continue;
+            }
+            let Some(original) = materials.get(handle.as_ref()) else {
+                continue;
+            };
+            let flashed = original.flashed(flash.color, scale);
+            let flashed = materials.add(flashed);
+            commands.entity(entity).insert(Flashing {
+                original: std::mem::replace(handle.as_mut(), flashed),
+                timer: Timer::new(flash.duration, TimerMode::Once),
+            });
+        }
+    }
+}
+
+fn update_flash<M: FlashMaterial>(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut flashing: Query<(Entity, &mut Flashing<M>, &mut Handle<M>)>,
+    mut materials: ResMut<Assets<M>>,
+) {
+    for (entity, mut flash, mut handle) in flashing.iter_mut() {
+        if !flash.timer.tick(time.delta()).finished() {
+            continue;
+        }
+        let flashed = std::mem::replace(handle.as_mut(), flash.original.clone());
+        materials.remove(flashed.id());
+        commands.entity(entity).remove::<Flashing<M>>();
+    }
+}
@@ -0,0 +1,139 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, BoxedFuture, LoadContext},
+    prelude::*,
+    reflect::TypePath,
+};
+use serde::Deserialize;
+
+use crate::{lighting::LightingProfile, tilemap::TilemapLevel};
+
+#[derive(Asset, TypePath, Debug)]
+pub struct Level {
+    pub name: String,
+    /// Scenes spawned at the root of the level, usually glTF scenes like `models/level.glb#Scene0`
+    pub scenes: Vec<Handle<Scene>>,
+    /// Tilemap levels spawned at the root of the level, like `maps/world.ldtk#Level_0`
+    pub tilemaps: Vec<Handle<TilemapLevel>>,
+    /// Replaces the default in game lighting while this level is played
+    pub lighting: Option<Handle<LightingProfile>>,
+    pub spawns: Vec<LevelSpawn>,
+    /// The path of the level played after this one
+    pub next: Option<String>,
+}
+
+/// A named point placed in a level, optionally with a scene or prefab spawned on it.
+#[derive(Debug, Clone)]
+pub struct LevelSpawn {
+    pub name: String,
+    pub translation: Vec3,
+    /// Euler angles, in degrees, applied in Y, X, Z order
+    pub rotation: Vec3,
+    pub scale: f32,
+    pub scene: Option<Handle<Scene>>,
+    /// The name of a prefab spawned on this point
+    pub prefab: Option<String>,
+}
+
+impl LevelSpawn {
+    pub fn transform(&self) -> Transform {
+        let [x, y, z] = self.rotation.to_array().map(f32::to_radians);
+        Transform::from_translation(self.translation)
+            .with_rotation(Quat::from_euler(EulerRot::YXZ, y, x, z))
+            .with_scale(Vec3::splat(self.scale))
+    }
+}
+
+#[derive(Deserialize)]
+struct LevelFile {
+    name: String,
+    #[serde(default)]
+    scenes: Vec<String>,
+    #[serde(default)]
+    tilemaps: Vec<String>,
+    #[serde(default)]
+    lighting: Option<String>,
+    #[serde(default)]
+    spawns: Vec<SpawnFile>,
+    #[serde(default)]
+    next: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SpawnFile {
+    name: String,
+    #[serde(default)]
+    translation: [f32; 3],
+    #[serde(default)]
+    rotation: [f32; 3],
+    #[serde(default = "default_scale")]
+    scale: f32,
+    #[serde(default)]
+    scene: Option<String>,
+    #[serde(default)]
+    prefab: Option<String>,
+}
+
+fn default_scale() -> f32 {
+    1.
+}
+
+#[derive(Default)]
+pub(super) struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    type Asset = Level;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let file: LevelFile = serde_yaml::from_slice(&bytes)?;
+
+            // Loading through the context makes these dependencies of the level,
+            // so the level only counts as loaded once they are too.
+            let scenes = file
+                .scenes
+                .into_iter()
+                .map(|path| load_context.load(path))
+                .collect();
+            let tilemaps = file
+                .tilemaps
+                .into_iter()
+                .map(|path| load_context.load(path))
+                .collect();
+            let lighting = file.lighting.map(|path| load_context.load(path));
+            let spawns = file
+                .spawns
+                .into_iter()
+                .map(|spawn| LevelSpawn {
+                    name: spawn.name,
+                    translation: Vec3::from(spawn.translation),
+                    rotation: Vec3::from(spawn.rotation),
+                    scale: spawn.scale,
+                    scene: spawn.scene.map(|path| load_context.load(path)),
+                    prefab: spawn.prefab,
+                })
+                .collect();
+
+            Ok(Level {
+                name: file.name,
+                scenes,
+                tilemaps,
+                lighting,
+                spawns,
+                next: file.next,
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.yaml"]
+    }
+}
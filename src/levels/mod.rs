@@ -0,0 +1,235 @@
+//! Data defined levels - `.level.yaml` assets listing the scenes, spawn points and lighting of a level.
+//!
+//! Entering the game loads the [`CurrentLevel`] in [`GameState::LoadingLevel`], spawns it under
+//! the [`InGame`] root once everything it depends on is loaded, and then moves to [`GameState::InGame`].
+use bevy::{
+    asset::{LoadState, RecursiveDependencyLoadState},
+    ecs::system::Command,
+    prelude::*,
+};
+use bevy_common_assets::yaml::YamlAssetPlugin;
+
+use crate::{
+    app_state::AppState,
+    assets::MainGameAssets,
+    in_game::{game_state::GameState, InGame},
+    lighting::ActiveLighting,
+    prefabs::SpawnPrefab,
+    tilemap::Tilemap,
+    ui::toasts::Notification,
+};
+
+mod loader;
+mod progress;
+
+pub use loader::Level;
+pub use progress::{LevelManifest, LevelProgress};
+
+/// The level a new game starts on.
+pub const FIRST_LEVEL: &str = "levels/level_1.level.yaml";
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Level>()
+            .init_asset_loader::<loader::LevelLoader>()
+            .add_plugins(YamlAssetPlugin::<LevelManifest>::new(&["levels.yaml"]))
+            .insert_resource(progress::load_progress())
+            .register_type::<LevelRoot>()
+            .register_type::<SpawnPoint>()
+            .add_systems(OnEnter(AppState::InGame), start_level)
+            .add_systems(OnEnter(GameState::LoadingLevel), load_level)
+            .add_systems(OnEnter(GameState::Complete), progress::complete_level)
+            .add_systems(
+                Update,
+                (
+                    spawn_level.run_if(in_state(GameState::LoadingLevel)),
+                    progress::save_progress.run_if(resource_changed::<LevelProgress>()),
+                ),
+            );
+    }
+}
+
+/// The level being played - set it before entering [`AppState::InGame`] to choose where the game starts.
+#[derive(Resource, Clone, Debug)]
+pub struct CurrentLevel {
+    pub path: String,
+    handle: Handle<Level>,
+}
+
+impl CurrentLevel {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            handle: Handle::default(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn handle(&self) -> &Handle<Level> {
+        &self.handle
+    }
+}
+
+impl Default for CurrentLevel {
+    fn default() -> Self {
+        Self::new(FIRST_LEVEL)
+    }
+}
+
+/// The entity everything spawned by the current level lives under.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct LevelRoot;
+
+/// A named point in the level, for gameplay code to spawn things at.
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component)]
+pub struct SpawnPoint(pub String);
+
+/// Replaces the current level with the one at `path`.
+pub struct LoadLevel {
+    pub path: String,
+}
+
+impl Command for LoadLevel {
+    fn apply(self, world: &mut World) {
+        let roots = world
+            .query_filtered::<Entity, With<LevelRoot>>()
+            .iter(world)
+            .collect::<Vec<_>>();
+        for root in roots {
+            world.entity_mut(root).despawn_recursive();
+        }
+        world.insert_resource(CurrentLevel::new(self.path));
+        world.insert_resource(NextState(Some(GameState::LoadingLevel)));
+    }
+}
+
+/// Starts the current level over.
+pub struct RestartLevel;
+
+impl Command for RestartLevel {
+    fn apply(self, world: &mut World) {
+        let path = world
+            .get_resource_or_insert_with(CurrentLevel::default)
+            .path
+            .clone();
+        LoadLevel { path }.apply(world);
+    }
+}
+
+/// Moves on to the level after the current one, or back to the main menu after the last level.
+pub struct NextLevel;
+
+impl Command for NextLevel {
+    fn apply(self, world: &mut World) {
+        let next = world.get_resource::<CurrentLevel>().and_then(|current| {
+            world
+                .resource::<Assets<Level>>()
+                .get(&current.handle)
+                .and_then(|level| level.next.clone())
+        });
+        match next {
+            Some(path) => LoadLevel { path }.apply(world),
+            None => {
+                world.send_event(Notification::info("All levels completed!"));
+                world.insert_resource(NextState(Some(AppState::MainMenu)));
+            }
+        }
+    }
+}
+
+fn start_level(mut commands: Commands, current: Option<Res<CurrentLevel>>) {
+    if current.is_none() {
+        commands.init_resource::<CurrentLevel>();
+    }
+    commands.insert_resource(NextState(Some(GameState::LoadingLevel)));
+}
+
+fn load_level(mut current: ResMut<CurrentLevel>, asset_server: Res<AssetServer>) {
+    current.handle = asset_server.load(current.path.clone());
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_level(
+    mut commands: Commands,
+    current: Res<CurrentLevel>,
+    asset_server: Res<AssetServer>,
+    assets: Res<MainGameAssets>,
+    levels: Res<Assets<Level>>,
+    root: Query<Entity, With<InGame>>,
+    mut notifications: EventWriter<Notification>,
+) {
+    let failed = asset_server.get_load_state(&current.handle) == Some(LoadState::Failed)
+        || asset_server.get_recursive_dependency_load_state(&current.handle)
+            == Some(RecursiveDependencyLoadState::Failed);
+    if failed {
+        error!("Couldn't load level {}", current.path);
+        notifications.send(Notification::error("Couldn't load the level"));
+        commands.insert_resource(NextState(Some(AppState::MainMenu)));
+        return;
+    }
+    if !asset_server.is_loaded_with_dependencies(&current.handle) {
+        return;
+    }
+    let (Some(level), Ok(root)) = (levels.get(&current.handle), root.get_single()) else {
+        return;
+    };
+
+    let lighting = level
+        .lighting
+        .clone()
+        .unwrap_or_else(|| assets.in_game_lighting.clone());
+    commands.insert_resource(ActiveLighting(lighting));
+
+    let mut prefabs = vec![];
+    commands.entity(root).with_children(|p| {
+        p.spawn((
+            Name::new(level.name.clone()),
+            LevelRoot,
+            TransformBundle::default(),
+            VisibilityBundle::default(),
+        ))
+        .with_children(|p| {
+            for scene in level.scenes.iter() {
+                p.spawn(SceneBundle {
+                    scene: scene.clone(),
+                    ..default()
+                });
+            }
+            for tilemap in level.tilemaps.iter() {
+                p.spawn((
+                    Name::new("Tilemap"),
+                    Tilemap(tilemap.clone()),
+                    SpatialBundle::default(),
+                ));
+            }
+            for spawn in level.spawns.iter() {
+                let mut entity = p.spawn((
+                    Name::new(spawn.name.clone()),
+                    SpawnPoint(spawn.name.clone()),
+                    TransformBundle::from_transform(spawn.transform()),
+                    VisibilityBundle::default(),
+                ));
+                if let Some(scene) = &spawn.scene {
+                    entity.with_children(|p| {
+                        p.spawn(SceneBundle {
+                            scene: scene.clone(),
+                            ..default()
+                        });
+                    });
+                }
+                if let Some(prefab) = &spawn.prefab {
+                    prefabs.push(SpawnPrefab::new(prefab.clone()).with_parent(entity.id()));
+                }
+            }
+        });
+    });
+    for prefab in prefabs {
+        commands.add(prefab);
+    }
+
+    commands.insert_resource(NextState(Some(GameState::InGame)));
+}
@@ -0,0 +1,91 @@
+use bevy::{prelude::*, reflect::TypePath, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::save::storage;
+
+use super::CurrentLevel;
+
+const PROGRESS_KEY: &str = "level_progress";
+
+/// The levels offered on the level select screen, in the order they unlock.
+#[derive(Deserialize, TypePath, Asset, Debug, Clone)]
+pub struct LevelManifest {
+    pub levels: Vec<LevelEntry>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LevelEntry {
+    pub name: String,
+    pub path: String,
+    /// Playable from the start, rather than once the level before it is completed
+    #[serde(default)]
+    pub unlocked: bool,
+}
+
+impl LevelManifest {
+    /// Whether the level at `index` can be played with the given progress.
+    pub fn is_unlocked(&self, index: usize, progress: &LevelProgress) -> bool {
+        let Some(entry) = self.levels.get(index) else {
+            return false;
+        };
+        index == 0
+            || entry.unlocked
+            || progress.is_completed(&entry.path)
+            || progress.is_completed(&self.levels[index - 1].path)
+    }
+}
+
+/// Which levels have been completed, persisted between sessions.
+/// Best scores live in the [`crate::score::HighScores`].
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct LevelProgress {
+    pub levels: HashMap<String, LevelRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+#[serde(default)]
+pub struct LevelRecord {
+    pub completed: bool,
+}
+
+impl LevelProgress {
+    pub fn get(&self, path: &str) -> LevelRecord {
+        self.levels.get(path).copied().unwrap_or_default()
+    }
+
+    pub fn is_completed(&self, path: &str) -> bool {
+        self.get(path).completed
+    }
+
+    pub fn complete(&mut self, path: &str) {
+        self.levels.entry(path.to_string()).or_default().completed = true;
+    }
+}
+
+pub(super) fn load_progress() -> LevelProgress {
+    match storage::read(PROGRESS_KEY) {
+        Ok(Some(contents)) => ron::from_str(&contents).unwrap_or_else(|e| {
+            warn!("Couldn't parse level progress, starting over: {e}");
+            LevelProgress::default()
+        }),
+        Ok(None) => LevelProgress::default(),
+        Err(e) => {
+            warn!("Couldn't read level progress, starting over: {e}");
+            LevelProgress::default()
+        }
+    }
+}
+
+pub(super) fn save_progress(progress: Res<LevelProgress>) {
+    let result = ron::ser::to_string_pretty(progress.as_ref(), Default::default())
+        .map_err(|e| e.to_string())
+        .and_then(|contents| storage::write(PROGRESS_KEY, &contents).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        error!("Couldn't save level progress: {e}");
+    }
+}
+
+pub(super) fn complete_level(mut progress: ResMut<LevelProgress>, current: Res<CurrentLevel>) {
+    progress.complete(&current.path);
+}
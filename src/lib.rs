@@ -1,7 +1,23 @@
 mod app_state;
 mod assets;
+mod camera;
+mod controllers;
+mod dialogue;
+mod health;
 mod in_game;
+mod juice;
+mod levels;
+mod lighting;
 mod menus;
+mod particles;
+mod physics;
+mod post_process;
+mod prefabs;
+mod save;
+mod score;
+mod settings;
+mod sprites;
+mod tilemap;
 mod toon_material;
 mod ui;
 
@@ -9,23 +25,39 @@ use std::time::Duration;
 
 use app_state::AppState;
 use assets::{MainGameAssetPlugin, MainGameAssets};
-use bevy::{
-    core_pipeline::{clear_color::ClearColorConfig, tonemapping::Tonemapping},
-    input::common_conditions::input_toggle_active,
-    prelude::*,
-};
+use bevy::{input::common_conditions::input_toggle_active, prelude::*};
 
 use bevy_inspector_egui::quick::{StateInspectorPlugin, WorldInspectorPlugin};
 use bevy_turborand::prelude::RngPlugin;
-use bevy_vector_shapes::Shape2dPlugin;
+use bevy_vector_shapes::ShapePlugin;
+use camera::CameraPlugin;
+use controllers::ControllersPlugin;
 use credits::CreditsPlugin;
 use dexterous_developer::{hot_bevy_main, InitialPlugins};
+use dialogue::DialoguePlugin;
+use health::HealthPlugin;
 use in_game::InGamePlugin;
+use juice::JuicePlugin;
+use levels::LevelPlugin;
+use lighting::LightingPlugin;
 use loading_state::LoadingScreenPlugin;
 use menu::MainMenuPlugin;
-use menus::{credits, loading_state, menu};
+use menus::{
+    credits, level_select::LevelSelectPlugin, loading_state, menu, quit::QuitPlugin,
+    save_slots::SaveSlotsPlugin, settings::SettingsScreenPlugin,
+};
+use particles::ParticlesPlugin;
+use physics::PhysicsPlugin;
+use post_process::PostProcessPlugin;
+use prefabs::PrefabPlugin;
+use save::SavePlugin;
+use score::ScorePlugin;
+use settings::SettingsPlugin;
+use sprites::SpritesPlugin;
+use tilemap::TilemapPlugin;
 
-use ui::{colors::DEFAULT_AMBIENT, UiPlugin};
+use toon_material::ToonMaterialPlugin;
+use ui::UiPlugin;
 
 #[hot_bevy_main]
 fn bevy_main(initial: impl InitialPlugins) {
@@ -44,12 +76,11 @@ fn bevy_main(initial: impl InitialPlugins) {
                     ..default()
                 })
                 .set(ImagePlugin::default_nearest()),
-            Shape2dPlugin::default(),
+            ShapePlugin::default(),
             WorldInspectorPlugin::new().run_if(input_toggle_active(false, KeyCode::F1)),
             RngPlugin::default(),
         ))
         .insert_resource(ClearColor(ui::colors::SCREEN_BACKGROUND_COLOR))
-        .insert_resource(DEFAULT_AMBIENT)
         .add_plugins((
             LoadingScreenPlugin,
             MainMenuPlugin,
@@ -57,6 +88,29 @@ fn bevy_main(initial: impl InitialPlugins) {
             InGamePlugin,
             MainGameAssetPlugin,
             UiPlugin,
+            QuitPlugin,
+            SavePlugin,
+            SaveSlotsPlugin,
+            ToonMaterialPlugin,
+            SettingsPlugin,
+            SettingsScreenPlugin,
+            CameraPlugin,
+            JuicePlugin,
+        ))
+        .add_plugins((
+            SpritesPlugin,
+            LightingPlugin,
+            ParticlesPlugin,
+            PostProcessPlugin,
+            LevelPlugin,
+            PrefabPlugin,
+            LevelSelectPlugin,
+            TilemapPlugin,
+            PhysicsPlugin,
+            ControllersPlugin,
+            HealthPlugin,
+            ScorePlugin,
+            DialoguePlugin,
         ))
         .add_state::<AppState>()
         .register_type::<AppState>()
@@ -65,44 +119,5 @@ fn bevy_main(initial: impl InitialPlugins) {
             StateInspectorPlugin::<AppState>::default()
                 .run_if(input_toggle_active(false, KeyCode::F1)),
         )
-        .add_systems(Startup, setup)
-        .add_systems(Update, fix_light)
         .run();
 }
-
-fn setup(mut commands: Commands) {
-    commands.spawn(Camera3dBundle {
-        transform: Transform::from_translation(Vec3::new(-2., 5., -5.))
-            .looking_at(Vec3::Y, Vec3::Y),
-        tonemapping: Tonemapping::AcesFitted,
-        ..default()
-    });
-
-    commands.spawn(Camera2dBundle {
-        camera: Camera {
-            order: 1,
-            ..default()
-        },
-        camera_2d: Camera2d {
-            clear_color: ClearColorConfig::None,
-        },
-        tonemapping: Tonemapping::AcesFitted,
-        ..default()
-    });
-}
-
-fn fix_light(
-    mut directional: Query<&mut DirectionalLight, Added<DirectionalLight>>,
-    mut point: Query<&mut PointLight, Added<PointLight>>,
-) {
-    for mut light in directional.iter_mut() {
-        if !light.shadows_enabled {
-            light.shadows_enabled = true;
-        }
-    }
-    for mut light in point.iter_mut() {
-        if !light.shadows_enabled {
-            light.shadows_enabled = true;
-        }
-    }
-}
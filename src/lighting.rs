@@ -0,0 +1,244 @@
+//! Lighting profiles - the ambient light, sun and fog for a level or state - loaded from `.light.yaml` assets.
+use bevy::{
+    pbr::{CascadeShadowConfigBuilder, DirectionalLightShadowMap},
+    prelude::*,
+    reflect::TypePath,
+};
+use bevy_common_assets::yaml::YamlAssetPlugin;
+use serde::Deserialize;
+
+use crate::{
+    app_state::AppState,
+    assets::MainGameAssets,
+    camera::MainCamera,
+    settings::{Settings, ShadowQuality},
+};
+
+pub struct LightingPlugin;
+
+impl Plugin for LightingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(YamlAssetPlugin::<LightingProfile>::new(&["light.yaml"]))
+            .init_resource::<ActiveLighting>()
+            .add_systems(OnExit(AppState::LoadingMenu), use_menu_lighting)
+            .add_systems(OnEnter(AppState::InGame), use_in_game_lighting)
+            .add_systems(OnExit(AppState::InGame), use_menu_lighting)
+            .add_systems(Update, (apply_lighting, apply_shadow_quality));
+    }
+}
+
+#[derive(Deserialize, TypePath, Asset, Clone, Debug)]
+pub struct LightingProfile {
+    pub ambient: Ambient,
+    #[serde(default)]
+    pub sun: Option<Sun>,
+    #[serde(default)]
+    pub fog: Option<Fog>,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct Ambient {
+    pub color: [f32; 3],
+    pub brightness: f32,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct Sun {
+    /// The direction the light travels in
+    pub direction: [f32; 3],
+    pub color: [f32; 3],
+    /// In lux
+    pub illuminance: f32,
+    #[serde(default)]
+    pub cascades: Cascades,
+}
+
+/// How the sun's shadows are split up - the shadow quality setting may reduce the number of cascades.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct Cascades {
+    pub count: usize,
+    pub first_cascade_far_bound: f32,
+    pub maximum_distance: f32,
+}
+
+impl Default for Cascades {
+    fn default() -> Self {
+        Self {
+            count: 4,
+            first_cascade_far_bound: 5.,
+            maximum_distance: 100.,
+        }
+    }
+}
+
+/// Linear distance fog on the main camera.
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct Fog {
+    pub color: [f32; 3],
+    pub start: f32,
+    pub end: f32,
+}
+
+/// The profile currently lighting the scene - replace it to switch lighting.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ActiveLighting(pub Handle<LightingProfile>);
+
+/// The directional light spawned for a profile's [`Sun`].
+#[derive(Component)]
+struct SunLight;
+
+/// Whether a light was authored to cast shadows, so turning shadows back on restores it.
+#[derive(Component)]
+struct CastsShadows(bool);
+
+fn use_menu_lighting(mut commands: Commands, assets: Res<MainGameAssets>) {
+    commands.insert_resource(ActiveLighting(assets.menu_lighting.clone()));
+}
+
+fn use_in_game_lighting(mut commands: Commands, assets: Res<MainGameAssets>) {
+    commands.insert_resource(ActiveLighting(assets.in_game_lighting.clone()));
+}
+
+fn rgb([r, g, b]: [f32; 3]) -> Color {
+    Color::rgb(r, g, b)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn apply_lighting(
+    mut commands: Commands,
+    active: Res<ActiveLighting>,
+    settings: Res<Settings>,
+    profiles: Res<Assets<LightingProfile>>,
+    mut events: EventReader<AssetEvent<LightingProfile>>,
+    mut ambient: ResMut<AmbientLight>,
+    suns: Query<Entity, With<SunLight>>,
+    cameras: Query<Entity, With<MainCamera>>,
+    mut built_quality: Local<Option<ShadowQuality>>,
+) {
+    let reloaded = events
+        .read()
+        .any(|e| e.is_loaded_with_dependencies(&active.0) || e.is_modified(&active.0));
+    let quality_changed = *built_quality != Some(settings.shadow_quality);
+    if !reloaded && !active.is_changed() && !quality_changed {
+        return;
+    }
+    let Some(profile) = profiles.get(&active.0) else {
+        return;
+    };
+    *built_quality = Some(settings.shadow_quality);
+
+    ambient.color = rgb(profile.ambient.color);
+    ambient.brightness = profile.ambient.brightness;
+
+    for sun in suns.iter() {
+        commands.entity(sun).despawn_recursive();
+    }
+    if let Some(sun) = profile.sun {
+        let direction = Vec3::from(sun.direction).normalize_or_zero();
+        let up = if direction.cross(Vec3::Y).length_squared() < f32::EPSILON {
+            Vec3::Z
+        } else {
+            Vec3::Y
+        };
+        let quality = settings.shadow_quality;
+        commands.spawn((
+            DirectionalLightBundle {
+                directional_light: DirectionalLight {
+                    color: rgb(sun.color),
+                    illuminance: sun.illuminance,
+                    shadows_enabled: quality.enabled(),
+                    ..default()
+                },
+                transform: Transform::default().looking_to(direction, up),
+                cascade_shadow_config: CascadeShadowConfigBuilder {
+                    num_cascades: sun.cascades.count.clamp(1, quality.max_cascades()),
+                    first_cascade_far_bound: sun.cascades.first_cascade_far_bound,
+                    maximum_distance: sun.cascades.maximum_distance,
+                    ..default()
+                }
+                .build(),
+                ..default()
+            },
+            SunLight,
+        ));
+    }
+
+    for camera in cameras.iter() {
+        match profile.fog {
+            Some(fog) => {
+                commands.entity(camera).insert(FogSettings {
+                    color: rgb(fog.color),
+                    falloff: FogFalloff::Linear {
+                        start: fog.start,
+                        end: fog.end,
+                    },
+                    ..default()
+                });
+            }
+            None => {
+                commands.entity(camera).remove::<FogSettings>();
+            }
+        }
+    }
+}
+
+/// Lights cast shadows as authored, unless the shadow quality setting turns them off.
+fn apply_shadow_quality(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut shadow_map: ResMut<DirectionalLightShadowMap>,
+    mut directional: Query<(Entity, &mut DirectionalLight, Option<&CastsShadows>)>,
+    mut point: Query<(Entity, &mut PointLight, Option<&CastsShadows>)>,
+    mut spot: Query<(Entity, &mut SpotLight, Option<&CastsShadows>)>,
+) {
+    let quality = settings.shadow_quality;
+    let enabled = quality.enabled();
+    let all = settings.is_changed();
+
+    if shadow_map.size != quality.map_size() {
+        shadow_map.size = quality.map_size();
+    }
+    for (entity, mut light, authored) in directional.iter_mut() {
+        if all || authored.is_none() {
+            let shadows = casts_shadows(&mut commands, entity, light.shadows_enabled, authored);
+            if light.shadows_enabled != (shadows && enabled) {
+                light.shadows_enabled = shadows && enabled;
+            }
+        }
+    }
+    for (entity, mut light, authored) in point.iter_mut() {
+        if all || authored.is_none() {
+            let shadows = casts_shadows(&mut commands, entity, light.shadows_enabled, authored);
+            if light.shadows_enabled != (shadows && enabled) {
+                light.shadows_enabled = shadows && enabled;
+            }
+        }
+    }
+    for (entity, mut light, authored) in spot.iter_mut() {
+        if all || authored.is_none() {
+            let shadows = casts_shadows(&mut commands, entity, light.shadows_enabled, authored);
+            if light.shadows_enabled != (shadows && enabled) {
+                light.shadows_enabled = shadows && enabled;
+            }
+        }
+    }
+}
+
+/// Records a newly seen light's authored shadow setting, returning it.
+fn casts_shadows(
+    commands: &mut Commands,
+    entity: Entity,
+    shadows_enabled: bool,
+    authored: Option<&CastsShadows>,
+) -> bool {
+    match authored {
+        Some(CastsShadows(shadows)) => *shadows,
+        None => {
+            commands
+                .entity(entity)
+                .insert(CastsShadows(shadows_enabled));
+            shadows_enabled
+        }
+    }
+}
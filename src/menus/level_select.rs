@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use bevy_ui_dsl::*;
+use bevy_ui_navigation::prelude::Focusable;
+use dexterous_developer::{
+    dexterous_developer_setup, ReloadableApp, ReloadableAppContents, ReloadableElementsSetup,
+};
+
+use crate::{
+    app_state::AppState,
+    assets::MainGameAssets,
+    levels::{CurrentLevel, LevelManifest, LevelProgress},
+    save::{first_free_slot, new_game_on_level},
+    score::HighScores,
+    ui::{
+        buttons::{focus_text_button, focused_button_activated, TypedFocusedButtonQuery},
+        classes::*,
+        colors::SCREEN_BACKGROUND_COLOR,
+        intermediary_node_bundles::*,
+        toasts::Notification,
+    },
+};
+
+use super::game_title;
+
+pub struct LevelSelectPlugin;
+
+impl Plugin for LevelSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.setup_reloadable_elements::<reloadable>();
+    }
+}
+
+#[dexterous_developer_setup(level_select)]
+fn reloadable(app: &mut ReloadableAppContents) {
+    app.reset_setup_in_state::<Screen, _, _>(AppState::LevelSelect, setup)
+        .add_systems(
+            Update,
+            (
+                process_keyboard_input,
+                focused_button_activated.pipe(process_input),
+            )
+                .run_if(in_state(AppState::LevelSelect)),
+        );
+}
+
+#[derive(Component)]
+struct Screen;
+
+#[derive(Component)]
+enum Buttons {
+    Level(String),
+    Back,
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    assets: Res<MainGameAssets>,
+    manifests: Res<Assets<LevelManifest>>,
+    progress: Res<LevelProgress>,
+    high_scores: Res<HighScores>,
+) {
+    commands.insert_resource(ClearColor(SCREEN_BACKGROUND_COLOR));
+
+    let levels = manifests
+        .get(&assets.level_manifest)
+        .map(|manifest| {
+            manifest
+                .levels
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| (entry.clone(), manifest.is_unlocked(index, &progress)))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let mut level_buttons = vec![];
+    let mut back_button = None;
+
+    let r = root((c_root, opaque.nb()), &asset_server, &mut commands, |p| {
+        node(primary_box, p, |p| {
+            game_title::game_title(p);
+            for (entry, unlocked) in levels {
+                let record = progress.get(&entry.path);
+                let mut label = entry.name.clone();
+                if !unlocked {
+                    label.push_str(" - Locked");
+                } else if record.completed {
+                    label.push_str(" - Completed");
+                }
+                if let Some(score) = high_scores.best(&entry.path) {
+                    label.push_str(&format!(" - Best {score}"));
+                }
+                let button = focus_text_button(
+                    label,
+                    (c_button.nb(), primary_box_item.nb()),
+                    apply_button_state,
+                    button_text,
+                    p,
+                );
+                level_buttons.push((button, entry.path, unlocked));
+            }
+            focus_text_button(
+                "Back",
+                (c_button.nb(), primary_box_item.nb()),
+                apply_button_state,
+                button_text,
+                p,
+            )
+            .set(&mut back_button);
+        });
+    });
+    commands.entity(r).insert(Screen);
+    for (button, path, unlocked) in level_buttons {
+        let mut button = commands.entity(button);
+        button.insert(Buttons::Level(path));
+        if !unlocked {
+            button.insert(Focusable::new().blocked());
+        }
+    }
+    commands.entity(back_button.unwrap()).insert(Buttons::Back);
+}
+
+fn process_input(
+    In(focused): In<Option<Entity>>,
+    mut commands: Commands,
+    interaction_query: TypedFocusedButtonQuery<'_, '_, '_, Buttons>,
+    mut notifications: EventWriter<Notification>,
+) {
+    let Some(focused) = focused else {
+        return;
+    };
+    let Some((_entity, btn)) = interaction_query.get(focused).ok() else {
+        return;
+    };
+    match btn {
+        Buttons::Level(path) => match first_free_slot() {
+            Some(slot) => new_game_on_level(&mut commands, slot, CurrentLevel::new(path.clone())),
+            // Rather than overwriting a save the player didn't choose, let them free one up
+            None => {
+                notifications.send(Notification::warning(
+                    "All save slots are full - delete one to start a new game",
+                ));
+                commands.insert_resource(NextState(Some(AppState::SaveSlots)));
+            }
+        },
+        Buttons::Back => commands.insert_resource(NextState(Some(AppState::MainMenu))),
+    };
+}
+
+fn process_keyboard_input(mut commands: Commands, keys: Res<Input<KeyCode>>) {
+    if keys.just_pressed(KeyCode::Escape) {
+        commands.insert_resource(NextState(Some(AppState::MainMenu)));
+    }
+}
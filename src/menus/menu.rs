@@ -4,6 +4,7 @@ use bevy_ui_dsl::*;
 use crate::{
     app_state::AppState,
     assets::MainGameAssets,
+    save::{continue_game, has_save, last_slot, SAVE_SLOTS},
     ui::{
         buttons::{focus_text_button, focused_button_activated, TypedFocusedButtonQuery},
         classes::*,
@@ -15,7 +16,10 @@ use dexterous_developer::{
     dexterous_developer_setup, ReloadableApp, ReloadableAppContents, ReloadableElementsSetup,
 };
 
-use super::game_title;
+use super::{
+    game_title,
+    quit::{quit_button, QuitButton},
+};
 pub struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
@@ -38,19 +42,39 @@ struct Screen;
 
 #[derive(Component)]
 enum Buttons {
+    Continue(usize),
     Start,
+    Load,
+    Settings,
     Credits,
 }
 
 fn setup(mut commands: Commands, _assets: Res<MainGameAssets>, asset_server: Res<AssetServer>) {
     commands.insert_resource(ClearColor(SCREEN_BACKGROUND_COLOR));
 
+    let last_slot = last_slot();
+    let has_saves = (0..SAVE_SLOTS).any(has_save);
+
+    let mut continue_button = None;
     let mut start_button = None;
+    let mut load_button = None;
+    let mut settings_button = None;
     let mut credits_button = None;
+    let mut quit = None;
 
     let r = root((c_root, opaque.nb()), &asset_server, &mut commands, |p| {
         node(primary_box, p, |p| {
             game_title::game_title(p);
+            if last_slot.is_some() {
+                focus_text_button(
+                    "Continue",
+                    (c_button.nb(), primary_box_item.nb()),
+                    apply_button_state,
+                    button_text,
+                    p,
+                )
+                .set(&mut continue_button);
+            }
             focus_text_button(
                 "Start Game",
                 (c_button.nb(), primary_box_item.nb()),
@@ -59,6 +83,24 @@ fn setup(mut commands: Commands, _assets: Res<MainGameAssets>, asset_server: Res
                 p,
             )
             .set(&mut start_button);
+            if has_saves {
+                focus_text_button(
+                    "Load Game",
+                    (c_button.nb(), primary_box_item.nb()),
+                    apply_button_state,
+                    button_text,
+                    p,
+                )
+                .set(&mut load_button);
+            }
+            focus_text_button(
+                "Settings",
+                (c_button.nb(), primary_box_item.nb()),
+                apply_button_state,
+                button_text,
+                p,
+            )
+            .set(&mut settings_button);
             focus_text_button(
                 "Credits",
                 (c_button.nb(), primary_box_item.nb()),
@@ -67,15 +109,28 @@ fn setup(mut commands: Commands, _assets: Res<MainGameAssets>, asset_server: Res
                 p,
             )
             .set(&mut credits_button);
+            quit = quit_button(p);
         });
     });
     commands.entity(r).insert(Screen);
+    if let (Some(button), Some(slot)) = (continue_button, last_slot) {
+        commands.entity(button).insert(Buttons::Continue(slot));
+    }
     commands
         .entity(start_button.unwrap())
         .insert(Buttons::Start);
+    if let Some(button) = load_button {
+        commands.entity(button).insert(Buttons::Load);
+    }
+    commands
+        .entity(settings_button.unwrap())
+        .insert(Buttons::Settings);
     commands
         .entity(credits_button.unwrap())
         .insert(Buttons::Credits);
+    if let Some(quit) = quit {
+        commands.entity(quit).insert(QuitButton);
+    }
 }
 
 fn process_input(
@@ -90,7 +145,10 @@ fn process_input(
         return;
     };
     match btn {
-        Buttons::Start => commands.insert_resource(NextState(Some(AppState::InGame))),
+        Buttons::Continue(slot) => continue_game(&mut commands, *slot),
+        Buttons::Start => commands.insert_resource(NextState(Some(AppState::LevelSelect))),
+        Buttons::Load => commands.insert_resource(NextState(Some(AppState::SaveSlots))),
+        Buttons::Settings => commands.insert_resource(NextState(Some(AppState::Settings))),
         Buttons::Credits => commands.insert_resource(NextState(Some(AppState::Credits))),
     };
 }
@@ -1,4 +1,8 @@
 pub mod credits;
 mod game_title;
+pub mod level_select;
 pub mod loading_state;
 pub mod menu;
+pub mod quit;
+pub mod save_slots;
+pub mod settings;
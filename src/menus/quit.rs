@@ -0,0 +1,213 @@
+use bevy::{app::AppExit, prelude::*};
+use bevy_ui_dsl::*;
+use bevy_ui_navigation::prelude::NavRequest;
+use dexterous_developer::{
+    dexterous_developer_setup, ReloadableApp, ReloadableAppContents, ReloadableElementsSetup,
+};
+
+use crate::{
+    app_state::AppState,
+    ui::{
+        buttons::{focus_text_button, focused_button_activated, TypedFocusedButtonQuery},
+        classes::*,
+        intermediary_node_bundles::*,
+        toasts::Notification,
+    },
+};
+
+pub struct QuitPlugin;
+
+impl Plugin for QuitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<QuitRequested>()
+            .add_state::<QuitState>()
+            .init_resource::<QuitSettings>()
+            .setup_reloadable_elements::<reloadable>();
+    }
+}
+
+#[dexterous_developer_setup(quit)]
+fn reloadable(app: &mut ReloadableAppContents) {
+    app.reset_setup_in_state::<Screen, _, _>(QuitState::Confirming, setup)
+        .add_systems(
+            Update,
+            (
+                focused_button_activated.pipe(process_quit_button),
+                handle_quit_requests,
+            )
+                .chain(),
+        )
+        .add_systems(
+            Update,
+            (
+                process_keyboard_input,
+                focused_button_activated.pipe(process_input),
+            )
+                .run_if(in_state(QuitState::Confirming)),
+        )
+        .add_systems(OnExit(AppState::InGame), cancel_confirmation);
+}
+
+/// Sent to leave the game - either exiting the app, or asking for confirmation first.
+#[derive(Event, Default, Clone, Copy, Debug)]
+pub struct QuitRequested;
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct QuitSettings {
+    /// Whether quitting while in game should ask the player to confirm first
+    pub confirm_in_game: bool,
+}
+
+impl Default for QuitSettings {
+    fn default() -> Self {
+        Self {
+            confirm_in_game: true,
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Copy, Debug, Hash, Default, States)]
+pub enum QuitState {
+    #[default]
+    None,
+    Confirming,
+}
+
+/// Marks a button that sends [`QuitRequested`] when activated.
+#[derive(Component)]
+pub struct QuitButton;
+
+#[derive(Component)]
+struct Screen;
+
+#[derive(Component)]
+enum Buttons {
+    Confirm,
+    Cancel,
+}
+
+/// Spawns a "Quit" button on native builds. There's nothing to quit to on the web,
+/// so no button is spawned there.
+pub fn quit_button(parent: &mut UiChildBuilder) -> Option<Entity> {
+    if cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    Some(focus_text_button(
+        "Quit",
+        (c_button.nb(), primary_box_item.nb()),
+        apply_button_state,
+        button_text,
+        parent,
+    ))
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, mut nav: EventWriter<NavRequest>) {
+    let mut confirm_button = None;
+    let mut cancel_button = None;
+    let r = root(
+        (overlay, modal, c_root),
+        &asset_server,
+        &mut commands,
+        |p| {
+            node(primary_box, p, |p| {
+                node((span.nb(), primary_box_main.nb()), p, |p| {
+                    text("Quit?", (), main_text, p);
+                });
+                text(
+                    "Any unsaved progress will be lost.",
+                    primary_box_item.nb(),
+                    standard_text,
+                    p,
+                );
+                focus_text_button(
+                    "Keep Playing",
+                    (c_button.nb(), primary_box_item.nb()),
+                    apply_button_state,
+                    button_text,
+                    p,
+                )
+                .set(&mut cancel_button);
+                focus_text_button(
+                    "Quit",
+                    (c_button.nb(), primary_box_item.nb()),
+                    apply_button_state,
+                    button_text,
+                    p,
+                )
+                .set(&mut confirm_button);
+            });
+        },
+    );
+    let cancel_button = cancel_button.unwrap();
+    commands.entity(r).insert(Screen);
+    commands.entity(cancel_button).insert(Buttons::Cancel);
+    commands
+        .entity(confirm_button.unwrap())
+        .insert(Buttons::Confirm);
+    nav.send(NavRequest::FocusOn(cancel_button));
+}
+
+fn process_quit_button(
+    In(focused): In<Option<Entity>>,
+    mut quit: EventWriter<QuitRequested>,
+    interaction_query: TypedFocusedButtonQuery<'_, '_, '_, QuitButton>,
+) {
+    let Some(focused) = focused else {
+        return;
+    };
+    if interaction_query.get(focused).is_ok() {
+        quit.send(QuitRequested);
+    }
+}
+
+fn handle_quit_requests(
+    mut commands: Commands,
+    mut requests: EventReader<QuitRequested>,
+    mut exit: EventWriter<AppExit>,
+    mut notifications: EventWriter<Notification>,
+    settings: Res<QuitSettings>,
+    app_state: Res<State<AppState>>,
+    quit_state: Res<State<QuitState>>,
+) {
+    if requests.read().count() == 0 {
+        return;
+    }
+    if cfg!(target_arch = "wasm32") {
+        notifications.send(Notification::info("Close this tab to return to the site."));
+    } else if settings.confirm_in_game
+        && app_state.get() == &AppState::InGame
+        && quit_state.get() == &QuitState::None
+    {
+        commands.insert_resource(NextState(Some(QuitState::Confirming)));
+    } else {
+        exit.send(AppExit);
+    }
+}
+
+fn process_input(
+    In(focused): In<Option<Entity>>,
+    mut commands: Commands,
+    mut exit: EventWriter<AppExit>,
+    interaction_query: TypedFocusedButtonQuery<'_, '_, '_, Buttons>,
+) {
+    let Some(focused) = focused else {
+        return;
+    };
+    let Some((_entity, btn)) = interaction_query.get(focused).ok() else {
+        return;
+    };
+    match btn {
+        Buttons::Confirm => exit.send(AppExit),
+        Buttons::Cancel => commands.insert_resource(NextState(Some(QuitState::None))),
+    };
+}
+
+fn process_keyboard_input(mut commands: Commands, keys: Res<Input<KeyCode>>) {
+    if keys.just_pressed(KeyCode::Escape) {
+        commands.insert_resource(NextState(Some(QuitState::None)));
+    }
+}
+
+fn cancel_confirmation(mut commands: Commands) {
+    commands.insert_resource(NextState(Some(QuitState::None)));
+}
@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use bevy_ui_dsl::*;
+use bevy_ui_navigation::prelude::{FocusState, Focusable, NavRequest};
+use dexterous_developer::{
+    dexterous_developer_setup, ReloadableApp, ReloadableAppContents, ReloadableElementsSetup,
+};
+
+use crate::{
+    app_state::AppState,
+    save::{continue_game, delete_save, has_save, new_game, SAVE_SLOTS},
+    ui::{
+        buttons::{focus_text_button, focused_button_activated, TypedFocusedButtonQuery},
+        classes::*,
+        colors::SCREEN_BACKGROUND_COLOR,
+        intermediary_node_bundles::*,
+        toasts::Notification,
+    },
+};
+
+use super::game_title;
+
+pub struct SaveSlotsPlugin;
+
+impl Plugin for SaveSlotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.setup_reloadable_elements::<reloadable>();
+    }
+}
+
+#[dexterous_developer_setup(save_slots)]
+fn reloadable(app: &mut ReloadableAppContents) {
+    app.reset_setup_in_state::<Screen, _, _>(AppState::SaveSlots, setup)
+        .add_systems(
+            Update,
+            (
+                process_keyboard_input,
+                focused_button_activated.pipe(process_input),
+                cancel_deletes,
+                update_labels,
+            )
+                .chain()
+                .run_if(in_state(AppState::SaveSlots)),
+        );
+}
+
+#[derive(Component)]
+struct Screen;
+
+#[derive(Component, Clone, Copy)]
+enum Buttons {
+    Slot {
+        slot: usize,
+        occupied: bool,
+    },
+    /// Deleting needs a second press to confirm
+    Delete {
+        slot: usize,
+        slot_button: Entity,
+        confirming: bool,
+    },
+    Back,
+}
+
+impl Buttons {
+    fn label(&self) -> String {
+        match self {
+            Buttons::Slot {
+                slot,
+                occupied: true,
+            } => format!("Slot {}", slot + 1),
+            Buttons::Slot {
+                slot,
+                occupied: false,
+            } => format!("Slot {} - Empty", slot + 1),
+            Buttons::Delete {
+                confirming: false, ..
+            } => "Delete".to_string(),
+            Buttons::Delete {
+                confirming: true, ..
+            } => "Confirm Delete".to_string(),
+            Buttons::Back => "Back".to_string(),
+        }
+    }
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ClearColor(SCREEN_BACKGROUND_COLOR));
+
+    let mut slot_buttons = vec![];
+    let mut back_button = None;
+
+    let r = root((c_root, opaque.nb()), &asset_server, &mut commands, |p| {
+        node(primary_box, p, |p| {
+            game_title::game_title(p);
+            for slot in 0..SAVE_SLOTS {
+                let occupied = has_save(slot);
+                node((span.nb(), primary_box_item.nb()), p, |p| {
+                    let button = Buttons::Slot { slot, occupied };
+                    let slot_button = focus_text_button(
+                        button.label(),
+                        c_button.nb(),
+                        apply_button_state,
+                        button_text,
+                        p,
+                    );
+                    slot_buttons.push((slot_button, button));
+                    if occupied {
+                        let button = Buttons::Delete {
+                            slot,
+                            slot_button,
+                            confirming: false,
+                        };
+                        let delete_button = focus_text_button(
+                            button.label(),
+                            c_button.nb(),
+                            apply_button_state,
+                            button_text,
+                            p,
+                        );
+                        slot_buttons.push((delete_button, button));
+                    }
+                });
+            }
+            focus_text_button(
+                "Back",
+                (c_button.nb(), primary_box_item.nb()),
+                apply_button_state,
+                button_text,
+                p,
+            )
+            .set(&mut back_button);
+        });
+    });
+    commands.entity(r).insert(Screen);
+    for (button, slot) in slot_buttons {
+        commands.entity(button).insert(slot);
+    }
+    commands.entity(back_button.unwrap()).insert(Buttons::Back);
+}
+
+fn process_input(
+    In(focused): In<Option<Entity>>,
+    mut commands: Commands,
+    interaction_query: TypedFocusedButtonQuery<'_, '_, '_, Buttons>,
+    mut nav: EventWriter<NavRequest>,
+    mut notifications: EventWriter<Notification>,
+) {
+    let Some(focused) = focused else {
+        return;
+    };
+    let Some((entity, btn)) = interaction_query.get(focused).ok() else {
+        return;
+    };
+    match btn {
+        Buttons::Slot {
+            slot,
+            occupied: true,
+        } => continue_game(&mut commands, *slot),
+        Buttons::Slot {
+            slot,
+            occupied: false,
+        } => new_game(&mut commands, *slot),
+        Buttons::Delete {
+            slot,
+            slot_button,
+            confirming: false,
+        } => {
+            commands.entity(entity).insert(Buttons::Delete {
+                slot: *slot,
+                slot_button: *slot_button,
+                confirming: true,
+            });
+        }
+        Buttons::Delete {
+            slot,
+            slot_button,
+            confirming: true,
+        } => match delete_save(*slot) {
+            Ok(_) => {
+                nav.send(NavRequest::FocusOn(*slot_button));
+                commands.entity(entity).despawn_recursive();
+                commands.entity(*slot_button).insert(Buttons::Slot {
+                    slot: *slot,
+                    occupied: false,
+                });
+                notifications.send(Notification::info("Save Deleted"));
+            }
+            Err(e) => {
+                error!("Couldn't delete slot {slot}: {e}");
+                notifications.send(Notification::error(format!("Couldn't delete: {e}")));
+            }
+        },
+        Buttons::Back => commands.insert_resource(NextState(Some(AppState::MainMenu))),
+    };
+}
+
+/// Moving away from a delete button takes back the first press.
+fn cancel_deletes(mut buttons: Query<(&mut Buttons, &Focusable), Changed<Focusable>>) {
+    for (mut button, focusable) in buttons.iter_mut() {
+        if let Buttons::Delete { confirming, .. } = button.as_mut() {
+            if *confirming && focusable.state() != FocusState::Focused {
+                *confirming = false;
+            }
+        }
+    }
+}
+
+fn update_labels(
+    buttons: Query<(&Buttons, &Children), Changed<Buttons>>,
+    mut texts: Query<&mut Text>,
+) {
+    for (button, children) in buttons.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                if let Some(section) = text.sections.first_mut() {
+                    section.value = button.label();
+                }
+            }
+        }
+    }
+}
+
+fn process_keyboard_input(mut commands: Commands, keys: Res<Input<KeyCode>>) {
+    if keys.just_pressed(KeyCode::Escape) {
+        commands.insert_resource(NextState(Some(AppState::MainMenu)));
+    }
+}
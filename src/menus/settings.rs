@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy_ui_dsl::*;
+use dexterous_developer::{
+    dexterous_developer_setup, ReloadableApp, ReloadableAppContents, ReloadableElementsSetup,
+};
+
+use crate::{
+    app_state::AppState,
+    settings::Settings,
+    ui::{
+        buttons::{focus_text_button, focused_button_activated, TypedFocusedButtonQuery},
+        classes::*,
+        colors::SCREEN_BACKGROUND_COLOR,
+        intermediary_node_bundles::*,
+    },
+};
+
+use super::game_title;
+
+pub struct SettingsScreenPlugin;
+
+impl Plugin for SettingsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.setup_reloadable_elements::<reloadable>();
+    }
+}
+
+#[dexterous_developer_setup(settings_screen)]
+fn reloadable(app: &mut ReloadableAppContents) {
+    app.reset_setup_in_state::<Screen, _, _>(AppState::Settings, setup)
+        .add_systems(
+            Update,
+            (
+                process_keyboard_input,
+                focused_button_activated.pipe(process_input),
+                update_labels.run_if(resource_changed::<Settings>()),
+            )
+                .run_if(in_state(AppState::Settings)),
+        );
+}
+
+#[derive(Component)]
+struct Screen;
+
+#[derive(Component, Clone, Copy)]
+enum Buttons {
+    Outlines,
+    ReducedMotion,
+    EffectsIntensity,
+    Shadows,
+    Palette,
+    Vignette,
+    ChromaticAberration,
+    FilmGrain,
+    Bloom,
+    Back,
+}
+
+const TOGGLES: [Buttons; 9] = [
+    Buttons::Outlines,
+    Buttons::ReducedMotion,
+    Buttons::EffectsIntensity,
+    Buttons::Shadows,
+    Buttons::Palette,
+    Buttons::Vignette,
+    Buttons::ChromaticAberration,
+    Buttons::FilmGrain,
+    Buttons::Bloom,
+];
+
+const EFFECT_INTENSITIES: [f32; 4] = [0.25, 0.5, 0.75, 1.];
+
+impl Buttons {
+    fn label(&self, settings: &Settings) -> String {
+        match self {
+            Buttons::Outlines => toggle_label("Outlines", settings.outlines),
+            Buttons::ReducedMotion => toggle_label("Reduced Motion", settings.reduced_motion),
+            Buttons::EffectsIntensity => {
+                format!("Screen Effects: {:.0}%", settings.effects_intensity * 100.)
+            }
+            Buttons::Shadows => format!("Shadows: {}", settings.shadow_quality.name()),
+            Buttons::Palette => toggle_label("Palette", settings.palette),
+            Buttons::Vignette => toggle_label("Vignette", settings.vignette),
+            Buttons::ChromaticAberration => {
+                toggle_label("Chromatic Aberration", settings.chromatic_aberration)
+            }
+            Buttons::FilmGrain => toggle_label("Film Grain", settings.film_grain),
+            Buttons::Bloom => format!("Bloom: {}", settings.bloom.name()),
+            Buttons::Back => "Back".to_string(),
+        }
+    }
+
+    fn toggle(&self, settings: &mut Settings) {
+        match self {
+            Buttons::Outlines => settings.outlines = !settings.outlines,
+            Buttons::ReducedMotion => settings.reduced_motion = !settings.reduced_motion,
+            Buttons::EffectsIntensity => {
+                settings.effects_intensity = EFFECT_INTENSITIES
+                    .into_iter()
+                    .find(|i| *i > settings.effects_intensity + f32::EPSILON)
+                    .unwrap_or(EFFECT_INTENSITIES[0]);
+            }
+            Buttons::Shadows => settings.shadow_quality = settings.shadow_quality.next(),
+            Buttons::Palette => settings.palette = !settings.palette,
+            Buttons::Vignette => settings.vignette = !settings.vignette,
+            Buttons::ChromaticAberration => {
+                settings.chromatic_aberration = !settings.chromatic_aberration
+            }
+            Buttons::FilmGrain => settings.film_grain = !settings.film_grain,
+            Buttons::Bloom => settings.bloom = settings.bloom.next(),
+            Buttons::Back => {}
+        }
+    }
+}
+
+fn toggle_label(name: &str, value: bool) -> String {
+    format!("{name}: {}", if value { "On" } else { "Off" })
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, settings: Res<Settings>) {
+    commands.insert_resource(ClearColor(SCREEN_BACKGROUND_COLOR));
+
+    let mut buttons = vec![];
+
+    let r = root((c_root, opaque.nb()), &asset_server, &mut commands, |p| {
+        node(primary_box, p, |p| {
+            game_title::game_title(p);
+            for button in TOGGLES.into_iter().chain([Buttons::Back]) {
+                let entity = focus_text_button(
+                    button.label(&settings),
+                    (c_button.nb(), primary_box_item.nb()),
+                    apply_button_state,
+                    button_text,
+                    p,
+                );
+                buttons.push((entity, button));
+            }
+        });
+    });
+    commands.entity(r).insert(Screen);
+    for (entity, button) in buttons {
+        commands.entity(entity).insert(button);
+    }
+}
+
+fn update_labels(
+    settings: Res<Settings>,
+    buttons: Query<(&Buttons, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    for (button, children) in buttons.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                if let Some(section) = text.sections.first_mut() {
+                    section.value = button.label(&settings);
+                }
+            }
+        }
+    }
+}
+
+fn process_input(
+    In(focused): In<Option<Entity>>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+    interaction_query: TypedFocusedButtonQuery<'_, '_, '_, Buttons>,
+) {
+    let Some(focused) = focused else {
+        return;
+    };
+    let Some((_entity, btn)) = interaction_query.get(focused).ok() else {
+        return;
+    };
+    match btn {
+        Buttons::Back => commands.insert_resource(NextState(Some(AppState::MainMenu))),
+        toggle => toggle.toggle(&mut settings),
+    };
+}
+
+fn process_keyboard_input(mut commands: Commands, keys: Res<Input<KeyCode>>) {
+    if keys.just_pressed(KeyCode::Escape) {
+        commands.insert_resource(NextState(Some(AppState::MainMenu)));
+    }
+}
@@ -0,0 +1,336 @@
+//! A lightweight CPU particle system. Effects are described in `.particles.yaml` assets,
+//! simulated by [`ParticleEmitter`]s and drawn with `bevy_vector_shapes`.
+use bevy::{ecs::system::Command, prelude::*, reflect::TypePath};
+use bevy_common_assets::yaml::YamlAssetPlugin;
+use bevy_turborand::prelude::*;
+use bevy_vector_shapes::prelude::*;
+use serde::Deserialize;
+
+use crate::in_game::{game_state::PauseState, InGame};
+
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(YamlAssetPlugin::<ParticleEffect>::new(&["particles.yaml"]))
+            .add_systems(
+                Update,
+                (
+                    simulate_particles.run_if(in_state(PauseState::None)),
+                    draw_particles,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Deserialize, TypePath, Asset, Clone, Debug)]
+pub struct ParticleEffect {
+    /// Particles spawned per second, for as long as the emitter is emitting
+    #[serde(default)]
+    pub rate: f32,
+    /// Particles spawned at once when the emitter starts
+    #[serde(default)]
+    pub burst: u32,
+    /// How long the emitter emits for, in seconds - emitters without one emit until they are despawned
+    #[serde(default)]
+    pub duration: Option<f32>,
+    /// The range a particle's lifetime is picked from, in seconds
+    pub lifetime: [f32; 2],
+    /// The range a particle's initial speed is picked from
+    pub speed: [f32; 2],
+    /// The centre of the cone particles are launched in, relative to the emitter
+    #[serde(default = "default_direction")]
+    pub direction: [f32; 3],
+    /// The angle, in radians, between the centre and the edge of the launch cone
+    #[serde(default)]
+    pub spread: f32,
+    #[serde(default)]
+    pub gravity: [f32; 3],
+    /// How much velocity is lost per second
+    #[serde(default)]
+    pub drag: f32,
+    /// Colour over the particle's life
+    pub color: Vec<Key<[f32; 4]>>,
+    /// Size over the particle's life
+    pub size: Vec<Key<f32>>,
+    #[serde(default)]
+    pub shape: ParticleShape,
+    #[serde(default = "default_max_particles")]
+    pub max_particles: usize,
+}
+
+fn default_direction() -> [f32; 3] {
+    [0., 1., 0.]
+}
+
+fn default_max_particles() -> usize {
+    256
+}
+
+/// A value at a point in a particle's life, from 0 (spawned) to 1 (expired).
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct Key<T> {
+    pub at: f32,
+    pub value: T,
+}
+
+/// The shape drawn for each particle, always facing the camera.
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub enum ParticleShape {
+    #[default]
+    Quad,
+    Circle,
+    Ring {
+        thickness: f32,
+    },
+    Polygon {
+        sides: u32,
+    },
+}
+
+trait Lerp: Copy {
+    fn lerp(self, other: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for [f32; 4] {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        [0, 1, 2, 3].map(|i| self[i].lerp(other[i], t))
+    }
+}
+
+fn sample<T: Lerp>(keys: &[Key<T>], at: f32, fallback: T) -> T {
+    let Some(first) = keys.first() else {
+        return fallback;
+    };
+    let mut previous = first;
+    for key in keys.iter() {
+        if key.at > at {
+            if key.at <= previous.at {
+                return key.value;
+            }
+            let t = ((at - previous.at) / (key.at - previous.at)).clamp(0., 1.);
+            return previous.value.lerp(key.value, t);
+        }
+        previous = key;
+    }
+    previous.value
+}
+
+fn range(rng: &mut GlobalRng, [min, max]: [f32; 2]) -> f32 {
+    min + (max - min) * rng.f32()
+}
+
+/// Emits and simulates the particles of an effect, in world space, from its entity's position.
+#[derive(Component, Debug)]
+pub struct ParticleEmitter {
+    pub effect: Handle<ParticleEffect>,
+    /// Stops new particles spawning, leaving the live ones to finish
+    pub emitting: bool,
+    /// Despawns the entity once it has stopped emitting and its particles have expired
+    pub despawn_when_done: bool,
+    particles: Vec<Particle>,
+    elapsed: f32,
+    pending: f32,
+    started: bool,
+}
+
+impl ParticleEmitter {
+    pub fn new(effect: Handle<ParticleEffect>) -> Self {
+        Self {
+            effect,
+            emitting: true,
+            despawn_when_done: false,
+            particles: vec![],
+            elapsed: 0.,
+            pending: 0.,
+            started: false,
+        }
+    }
+
+    pub fn one_shot(effect: Handle<ParticleEffect>) -> Self {
+        Self {
+            despawn_when_done: true,
+            ..Self::new(effect)
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn particle_count(&self) -> usize {
+        self.particles.len()
+    }
+
+    fn spawn(&mut self, effect: &ParticleEffect, origin: &GlobalTransform, rng: &mut GlobalRng) {
+        if self.particles.len() >= effect.max_particles {
+            return;
+        }
+        let (_, rotation, position) = origin.to_scale_rotation_translation();
+        let axis = (rotation * Vec3::from(effect.direction)).normalize_or_zero();
+        let tilt =
+            Quat::from_rotation_arc(Vec3::Y, if axis == Vec3::ZERO { Vec3::Y } else { axis });
+        let angle = effect.spread * rng.f32().sqrt();
+        let around = std::f32::consts::TAU * rng.f32();
+        let direction =
+            tilt * Quat::from_rotation_y(around) * Quat::from_rotation_x(angle) * Vec3::Y;
+        let lifetime = range(rng, effect.lifetime).max(f32::EPSILON);
+
+        self.particles.push(Particle {
+            position,
+            velocity: direction * range(rng, effect.speed),
+            age: 0.,
+            lifetime,
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Particle {
+    position: Vec3,
+    velocity: Vec3,
+    age: f32,
+    lifetime: f32,
+}
+
+/// Spawns a one shot [`ParticleEmitter`] at a position, under the [`InGame`] root if there is one.
+#[allow(dead_code)]
+pub struct SpawnParticles {
+    pub effect: Handle<ParticleEffect>,
+    pub transform: Transform,
+}
+
+impl SpawnParticles {
+    #[allow(dead_code)]
+    pub fn new(effect: Handle<ParticleEffect>, position: Vec3) -> Self {
+        Self {
+            effect,
+            transform: Transform::from_translation(position),
+        }
+    }
+}
+
+impl Command for SpawnParticles {
+    fn apply(self, world: &mut World) {
+        let root = world
+            .query_filtered::<Entity, With<InGame>>()
+            .iter(world)
+            .next();
+        let emitter = world
+            .spawn((
+                Name::new("Particles"),
+                // The root sits at the origin, so the global transform is known before propagation.
+                TransformBundle {
+                    local: self.transform,
+                    global: GlobalTransform::from(self.transform),
+                },
+                ParticleEmitter::one_shot(self.effect),
+            ))
+            .id();
+        if let Some(root) = root {
+            world.entity_mut(root).add_child(emitter);
+        }
+    }
+}
+
+fn simulate_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    effects: Res<Assets<ParticleEffect>>,
+    mut rng: ResMut<GlobalRng>,
+    mut emitters: Query<(Entity, &mut ParticleEmitter, &GlobalTransform)>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut emitter, origin) in emitters.iter_mut() {
+        let Some(effect) = effects.get(&emitter.effect) else {
+            continue;
+        };
+        let emitter = emitter.as_mut();
+
+        let gravity = Vec3::from(effect.gravity);
+        let drag = (1. - effect.drag * delta).max(0.);
+        emitter.particles.retain_mut(|particle| {
+            particle.age += delta;
+            particle.velocity = (particle.velocity + gravity * delta) * drag;
+            particle.position += particle.velocity * delta;
+            particle.age < particle.lifetime
+        });
+
+        if emitter.emitting {
+            if !emitter.started {
+                emitter.started = true;
+                for _ in 0..effect.burst {
+                    emitter.spawn(effect, origin, &mut rng);
+                }
+            }
+            emitter.pending += effect.rate * delta;
+            while emitter.pending >= 1. {
+                emitter.pending -= 1.;
+                emitter.spawn(effect, origin, &mut rng);
+            }
+            emitter.elapsed += delta;
+            if effect.duration.is_some_and(|d| emitter.elapsed >= d) {
+                emitter.emitting = false;
+            }
+        }
+
+        if emitter.despawn_when_done && !emitter.emitting && emitter.particles.is_empty() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn draw_particles(
+    mut painter: ShapePainter,
+    effects: Res<Assets<ParticleEffect>>,
+    emitters: Query<&ParticleEmitter>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+) {
+    // Without a 3D camera the particles are drawn in 2D, where they already face the camera.
+    let rotation = match camera.iter().next() {
+        Some(camera) => {
+            painter.set_3d();
+            camera.compute_transform().rotation
+        }
+        None => {
+            painter.set_2d();
+            Quat::IDENTITY
+        }
+    };
+
+    for emitter in emitters.iter() {
+        let Some(effect) = effects.get(&emitter.effect) else {
+            continue;
+        };
+        for particle in emitter.particles.iter() {
+            let life = particle.age / particle.lifetime;
+            let [r, g, b, a] = sample(&effect.color, life, [1., 1., 1., 1.]);
+            let size = sample(&effect.size, life, 1.);
+
+            painter.transform =
+                Transform::from_translation(particle.position).with_rotation(rotation);
+            painter.color = Color::rgba(r, g, b, a);
+            painter.hollow = false;
+            match effect.shape {
+                ParticleShape::Quad => {
+                    painter.rect(Vec2::splat(size));
+                }
+                ParticleShape::Circle => {
+                    painter.circle(size / 2.);
+                }
+                ParticleShape::Ring { thickness } => {
+                    painter.hollow = true;
+                    painter.thickness = thickness;
+                    painter.circle(size / 2.);
+                }
+                ParticleShape::Polygon { sides } => {
+                    painter.ngon(sides.max(3) as f32, size / 2.);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//! A small, deterministic 2D physics layer for platformers and top-down games.
+//!
+//! Bodies move in the XY plane in [`InGameFixedUpdate`], so physics stops while the game is paused.
+//! Moving bodies slide along whatever they hit rather than bouncing, and [`Sensor`]s report
+//! entities entering and leaving them with [`TriggerEntered`] and [`TriggerExited`].
+//! Moving bodies take their parent's position into account, but not its rotation or scale.
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{health::Respawning, in_game::InGameFixedUpdate, tilemap::TileCollider};
+
+mod query;
+mod shapes;
+
+// For game code - nothing in the template casts rays yet
+#[allow(unused_imports)]
+pub use query::{RayHit, SpatialQuery};
+use shapes::Shape;
+
+/// The most steps a body's movement is split into in a single update, to stop it passing through thin colliders.
+const MAX_SUBSTEPS: usize = 8;
+/// How many times overlaps are resolved in each step.
+const SOLVER_ITERATIONS: usize = 4;
+
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsSettings>()
+            .init_resource::<TriggerOverlaps>()
+            .add_event::<TriggerEntered>()
+            .add_event::<TriggerExited>()
+            .register_type::<RigidBody>()
+            .register_type::<Collider>()
+            .register_type::<CollisionLayers>()
+            .register_type::<Velocity>()
+            .register_type::<GravityScale>()
+            .register_type::<Sensor>()
+            .configure_sets(
+                InGameFixedUpdate,
+                (PhysicsSet::Prepare, PhysicsSet::Step, PhysicsSet::Triggers).chain(),
+            )
+            .add_systems(
+                InGameFixedUpdate,
+                (
+                    add_tile_colliders.in_set(PhysicsSet::Prepare),
+                    step.in_set(PhysicsSet::Step),
+                    detect_triggers.in_set(PhysicsSet::Triggers),
+                ),
+            );
+    }
+}
+
+/// Gameplay that sets velocities should run before [`PhysicsSet::Step`], and anything reacting to
+/// collisions after it.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PhysicsSet {
+    Prepare,
+    Step,
+    Triggers,
+}
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct PhysicsSettings {
+    /// Applied to dynamic bodies, in units per second squared - set it to zero for top-down games
+    pub gravity: Vec2,
+}
+
+impl Default for PhysicsSettings {
+    fn default() -> Self {
+        Self {
+            gravity: Vec2::new(0., -600.),
+        }
+    }
+}
+
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub enum RigidBody {
+    /// Never moves - walls, floors and tiles
+    #[default]
+    Static,
+    /// Moves with its velocity and is stopped by static bodies, but ignores gravity
+    Kinematic,
+    /// Falls with gravity and is stopped by static and kinematic bodies
+    Dynamic,
+}
+
+/// The shape of a body or [`Sensor`]. Colliders without either are ignored.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub enum Collider {
+    Aabb {
+        half_extents: Vec2,
+    },
+    Circle {
+        radius: f32,
+    },
+    /// Upright, with `half_height` measured between the centres of its rounded ends
+    Capsule {
+        half_height: f32,
+        radius: f32,
+    },
+}
+
+impl Default for Collider {
+    fn default() -> Self {
+        Self::Aabb {
+            half_extents: Vec2::splat(0.5),
+        }
+    }
+}
+
+/// Which layers a collider is on, and which layers it collides with.
+/// Two colliders only interact if each one's filters include one of the other's memberships.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct CollisionLayers {
+    pub memberships: u32,
+    pub filters: u32,
+}
+
+impl CollisionLayers {
+    pub const ALL: Self = Self {
+        memberships: u32::MAX,
+        filters: u32::MAX,
+    };
+
+    #[allow(dead_code)]
+    pub fn new(memberships: u32, filters: u32) -> Self {
+        Self {
+            memberships,
+            filters,
+        }
+    }
+
+    pub fn interacts_with(&self, other: &Self) -> bool {
+        self.memberships & other.filters != 0 && other.memberships & self.filters != 0
+    }
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, Deref, DerefMut)]
+#[reflect(Component)]
+pub struct Velocity(pub Vec2);
+
+#[derive(Component, Reflect, Clone, Copy, Debug, Deref, DerefMut)]
+#[reflect(Component)]
+pub struct GravityScale(pub f32);
+
+impl Default for GravityScale {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+/// Makes a collider a trigger volume - it reports overlaps instead of blocking movement.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct Sensor;
+
+/// What a moving body touched during the last step, added to it automatically.
+#[derive(Component, Clone, Debug, Default)]
+pub struct Contacts {
+    pub contacts: Vec<Contact>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Contact {
+    pub entity: Entity,
+    /// Pointing away from the other collider
+    pub normal: Vec2,
+}
+
+impl Contacts {
+    /// Whether the body is standing on something.
+    pub fn on_ground(&self) -> bool {
+        self.contacts.iter().any(|contact| contact.normal.y > 0.7)
+    }
+
+    #[allow(dead_code)]
+    pub fn on_ceiling(&self) -> bool {
+        self.contacts.iter().any(|contact| contact.normal.y < -0.7)
+    }
+
+    #[allow(dead_code)]
+    pub fn on_wall(&self) -> bool {
+        self.contacts
+            .iter()
+            .any(|contact| contact.normal.x.abs() > 0.7)
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct TriggerEntered {
+    pub sensor: Entity,
+    pub entity: Entity,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+#[allow(dead_code)]
+pub struct TriggerExited {
+    pub sensor: Entity,
+    pub entity: Entity,
+}
+
+#[derive(Resource, Default)]
+struct TriggerOverlaps(HashSet<(Entity, Entity)>);
+
+/// A collider's position in the world, from its parent's global transform and its own translation.
+/// Unlike [`GlobalTransform`], this is up to date between fixed steps.
+fn world_position(
+    transform: &Transform,
+    parent: Option<&Parent>,
+    globals: &Query<&GlobalTransform>,
+) -> Vec2 {
+    let offset = parent
+        .and_then(|parent| globals.get(parent.get()).ok())
+        .map(|global| global.translation())
+        .unwrap_or_default();
+    (offset + transform.translation).truncate()
+}
+
+fn add_tile_colliders(
+    mut commands: Commands,
+    tiles: Query<(Entity, &TileCollider), Added<TileCollider>>,
+) {
+    for (entity, tile) in tiles.iter() {
+        commands.entity(entity).insert((
+            RigidBody::Static,
+            Collider::Aabb {
+                half_extents: tile.size / 2.,
+            },
+        ));
+    }
+}
+
+struct Body {
+    entity: Entity,
+    kind: RigidBody,
+    shape: Shape,
+    layers: CollisionLayers,
+    sensor: bool,
+}
+
+type BodyQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static RigidBody,
+        &'static Collider,
+        &'static mut Transform,
+        Option<&'static Parent>,
+        Option<&'static CollisionLayers>,
+        Option<&'static mut Velocity>,
+        Option<&'static GravityScale>,
+        Option<&'static mut Contacts>,
+        Has<Sensor>,
+    ),
+    Without<Respawning>,
+>;
+
+fn step(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<PhysicsSettings>,
+    mut bodies: BodyQuery,
+    globals: Query<&GlobalTransform>,
+) {
+    let delta = time.delta_seconds();
+
+    let mut world = bodies
+        .iter()
+        .map(
+            |(entity, kind, collider, transform, parent, layers, _, _, _, sensor)| Body {
+                entity,
+                kind: *kind,
+                shape: Shape::new(collider, world_position(transform, parent, &globals)),
+                layers: layers.copied().unwrap_or_default(),
+                sensor,
+            },
+        )
+        .collect::<Vec<_>>();
+    // Sorting keeps the results the same regardless of how the ECS stores the bodies
+    world.sort_by_key(|body| body.entity);
+
+    for index in 0..world.len() {
+        let body = &world[index];
+        if body.kind == RigidBody::Static || body.sensor {
+            continue;
+        }
+        let Ok((entity, _, _, mut transform, _, _, velocity, gravity_scale, contacts, _)) =
+            bodies.get_mut(body.entity)
+        else {
+            continue;
+        };
+
+        let mut velocity_value = velocity.as_deref().map(|v| v.0).unwrap_or_default();
+        if body.kind == RigidBody::Dynamic {
+            let scale = gravity_scale.map(|scale| scale.0).unwrap_or(1.);
+            velocity_value += settings.gravity * scale * delta;
+        }
+
+        let start = body.shape.position;
+        let mut shape = body.shape;
+        let mut touching = vec![];
+        let motion = velocity_value * delta;
+        let smallest = shape.half_extents().min_element().max(0.01);
+        let substeps = ((motion.length() / smallest).ceil() as usize).clamp(1, MAX_SUBSTEPS);
+
+        for _ in 0..substeps {
+            shape.position += motion / substeps as f32;
+            for _ in 0..SOLVER_ITERATIONS {
+                let mut resolved = false;
+                for other in world.iter() {
+                    if other.entity == entity
+                        || other.sensor
+                        || !blocks(body.kind, other.kind)
+                        || !body.layers.interacts_with(&other.layers)
+                    {
+                        continue;
+                    }
+                    let Some((normal, depth)) = shapes::penetration(&shape, &other.shape) else {
+                        continue;
+                    };
+                    shape.position += normal * depth;
+                    // Stop moving into the collider, but keep sliding along it
+                    velocity_value -= normal * velocity_value.dot(normal).min(0.);
+                    if !touching
+                        .iter()
+                        .any(|contact: &Contact| contact.entity == other.entity)
+                    {
+                        touching.push(Contact {
+                            entity: other.entity,
+                            normal,
+                        });
+                    }
+                    resolved = true;
+                }
+                if !resolved {
+                    break;
+                }
+            }
+        }
+
+        transform.translation += (shape.position - start).extend(0.);
+        if let Some(mut velocity) = velocity {
+            velocity.0 = velocity_value;
+        }
+        match contacts {
+            Some(mut contacts) => contacts.contacts = touching,
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(Contacts { contacts: touching });
+            }
+        }
+        world[index].shape = shape;
+    }
+}
+
+/// Whether a moving body of one kind is stopped by a body of another.
+fn blocks(moving: RigidBody, other: RigidBody) -> bool {
+    match moving {
+        RigidBody::Static => false,
+        RigidBody::Kinematic => other == RigidBody::Static,
+        RigidBody::Dynamic => other != RigidBody::Dynamic,
+    }
+}
+
+type TriggerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Collider,
+        &'static Transform,
+        Option<&'static Parent>,
+        Option<&'static CollisionLayers>,
+        Has<Sensor>,
+    ),
+    Without<Respawning>,
+>;
+
+fn detect_triggers(
+    mut overlaps: ResMut<TriggerOverlaps>,
+    colliders: TriggerQuery,
+    globals: Query<&GlobalTransform>,
+    mut entered: EventWriter<TriggerEntered>,
+    mut exited: EventWriter<TriggerExited>,
+) {
+    let mut sensors = vec![];
+    let mut others = vec![];
+    for (entity, collider, transform, parent, layers, sensor) in colliders.iter() {
+        let shape = Shape::new(collider, world_position(transform, parent, &globals));
+        let layers = layers.copied().unwrap_or_default();
+        if sensor {
+            sensors.push((entity, shape, layers));
+        } else {
+            others.push((entity, shape, layers));
+        }
+    }
+
+    let mut current = vec![];
+    for (sensor, sensor_shape, sensor_layers) in sensors.iter() {
+        for (entity, shape, layers) in others.iter() {
+            if sensor_layers.interacts_with(layers)
+                && shapes::penetration(shape, sensor_shape).is_some()
+            {
+                current.push((*sensor, *entity));
+            }
+        }
+    }
+    current.sort();
+
+    let mut exits = overlaps
+        .0
+        .iter()
+        .filter(|pair| !current.contains(pair))
+        .copied()
+        .collect::<Vec<_>>();
+    exits.sort();
+    for (sensor, entity) in exits {
+        exited.send(TriggerExited { sensor, entity });
+    }
+    for (sensor, entity) in current.iter() {
+        if !overlaps.0.contains(&(*sensor, *entity)) {
+            entered.send(TriggerEntered {
+                sensor: *sensor,
+                entity: *entity,
+            });
+        }
+    }
+    overlaps.0 = current.into_iter().collect();
+}
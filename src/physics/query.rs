@@ -0,0 +1,75 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::health::Respawning;
+
+use super::{shapes, world_position, Collider, CollisionLayers, Sensor};
+
+type SolidColliderQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Collider,
+        &'static Transform,
+        Option<&'static Parent>,
+        Option<&'static CollisionLayers>,
+    ),
+    (Without<Sensor>, Without<Respawning>),
+>;
+
+/// Looks up colliders in the world - add it to a system's parameters to cast rays.
+#[derive(SystemParam)]
+pub struct SpatialQuery<'w, 's> {
+    colliders: SolidColliderQuery<'w, 's>,
+    globals: Query<'w, 's, &'static GlobalTransform>,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[allow(dead_code)]
+pub struct RayHit {
+    pub entity: Entity,
+    pub point: Vec2,
+    pub normal: Vec2,
+    pub distance: f32,
+}
+
+impl<'w, 's> SpatialQuery<'w, 's> {
+    /// Finds the closest collider along a ray, ignoring sensors, colliders not on one of the
+    /// `layers`, and the `exclude`d entities.
+    #[allow(dead_code)]
+    pub fn raycast(
+        &self,
+        origin: Vec2,
+        direction: Vec2,
+        max_distance: f32,
+        layers: u32,
+        exclude: &[Entity],
+    ) -> Option<RayHit> {
+        let direction = direction.try_normalize()?;
+        let mut closest: Option<RayHit> = None;
+        for (entity, collider, transform, parent, collision_layers) in self.colliders.iter() {
+            let memberships = collision_layers.copied().unwrap_or_default().memberships;
+            if memberships & layers == 0 || exclude.contains(&entity) {
+                continue;
+            }
+            let shape =
+                shapes::Shape::new(collider, world_position(transform, parent, &self.globals));
+            let Some((distance, normal)) = shapes::raycast(&shape, origin, direction) else {
+                continue;
+            };
+            let closer = match closest {
+                Some(hit) => (distance, entity) < (hit.distance, hit.entity),
+                None => true,
+            };
+            if distance <= max_distance && closer {
+                closest = Some(RayHit {
+                    entity,
+                    point: origin + direction * distance,
+                    normal,
+                    distance,
+                });
+            }
+        }
+        closest
+    }
+}
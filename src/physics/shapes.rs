@@ -0,0 +1,220 @@
+//! Overlap and raycast tests between colliders.
+//!
+//! Every collider is treated as a core - a box or a vertical segment - grown by a radius, so a circle
+//! is a segment with no length and a capsule is a segment with some.
+use bevy::prelude::*;
+
+use super::Collider;
+
+#[derive(Clone, Copy, Debug)]
+pub(super) enum Core {
+    Box(Vec2),
+    Segment(f32),
+}
+
+/// A collider placed in the world.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Shape {
+    pub position: Vec2,
+    pub core: Core,
+    pub radius: f32,
+}
+
+impl Shape {
+    pub fn new(collider: &Collider, position: Vec2) -> Self {
+        let (core, radius) = match *collider {
+            Collider::Aabb { half_extents } => (Core::Box(half_extents), 0.),
+            Collider::Circle { radius } => (Core::Segment(0.), radius),
+            Collider::Capsule {
+                half_height,
+                radius,
+            } => (Core::Segment(half_height), radius),
+        };
+        Self {
+            position,
+            core,
+            radius,
+        }
+    }
+
+    /// Half the size of the shape's bounding box.
+    pub fn half_extents(&self) -> Vec2 {
+        match self.core {
+            Core::Box(half) => half,
+            Core::Segment(half_height) => Vec2::new(self.radius, half_height + self.radius),
+        }
+    }
+}
+
+/// How far `a` needs to move, and in which direction, to stop overlapping `b`.
+pub(super) fn penetration(a: &Shape, b: &Shape) -> Option<(Vec2, f32)> {
+    match (a.core, b.core) {
+        (Core::Box(ha), Core::Box(hb)) => box_box(a.position, ha, b.position, hb),
+        (Core::Segment(ha), Core::Segment(hb)) => segment_segment(a, ha, b, hb),
+        (Core::Segment(half_height), Core::Box(half)) => {
+            segment_box(a.position, half_height, a.radius, b.position, half)
+        }
+        (Core::Box(half), Core::Segment(half_height)) => {
+            segment_box(b.position, half_height, b.radius, a.position, half)
+                .map(|(normal, depth)| (-normal, depth))
+        }
+    }
+}
+
+fn box_box(a: Vec2, ha: Vec2, b: Vec2, hb: Vec2) -> Option<(Vec2, f32)> {
+    let delta = a - b;
+    let overlap = ha + hb - delta.abs();
+    if overlap.x <= 0. || overlap.y <= 0. {
+        return None;
+    }
+    if overlap.x < overlap.y {
+        Some((Vec2::X * sign(delta.x), overlap.x))
+    } else {
+        Some((Vec2::Y * sign(delta.y), overlap.y))
+    }
+}
+
+fn segment_segment(a: &Shape, ha: f32, b: &Shape, hb: f32) -> Option<(Vec2, f32)> {
+    let (a_bottom, a_top) = (a.position.y - ha, a.position.y + ha);
+    let (b_bottom, b_top) = (b.position.y - hb, b.position.y + hb);
+    let (ya, yb) = if a_bottom > b_top {
+        (a_bottom, b_top)
+    } else if b_bottom > a_top {
+        (a_top, b_bottom)
+    } else {
+        let y = (a_bottom.max(b_bottom) + a_top.min(b_top)) / 2.;
+        (y, y)
+    };
+    let delta = Vec2::new(a.position.x, ya) - Vec2::new(b.position.x, yb);
+    let distance = delta.length();
+    let radius = a.radius + b.radius;
+    if distance >= radius {
+        return None;
+    }
+    let normal = if distance > f32::EPSILON {
+        delta / distance
+    } else {
+        Vec2::Y
+    };
+    Some((normal, radius - distance))
+}
+
+/// Pushes a rounded vertical segment out of a box.
+fn segment_box(
+    position: Vec2,
+    half_height: f32,
+    radius: f32,
+    center: Vec2,
+    half: Vec2,
+) -> Option<(Vec2, f32)> {
+    let (bottom, top) = (position.y - half_height, position.y + half_height);
+    let (min, max) = (center - half, center + half);
+
+    // The point on the segment closest to the box
+    let y = if bottom > max.y {
+        bottom
+    } else if top < min.y {
+        top
+    } else {
+        center.y.clamp(bottom.max(min.y), top.min(max.y))
+    };
+    let point = Vec2::new(position.x, y);
+    let closest = point.clamp(min, max);
+
+    if closest != point {
+        let delta = point - closest;
+        let distance = delta.length();
+        return (distance < radius).then(|| (delta / distance, radius - distance));
+    }
+
+    // The segment reaches inside the box, so push it out through the nearest face
+    [
+        (Vec2::NEG_X, point.x - min.x + radius),
+        (Vec2::X, max.x - point.x + radius),
+        (Vec2::NEG_Y, top - min.y + radius),
+        (Vec2::Y, max.y - bottom + radius),
+    ]
+    .into_iter()
+    .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Where a ray first hits a shape, as the distance along it and the surface normal.
+/// Rays starting inside a shape hit it straight away.
+pub(super) fn raycast(shape: &Shape, origin: Vec2, direction: Vec2) -> Option<(f32, Vec2)> {
+    match shape.core {
+        Core::Box(half) => ray_box(origin, direction, shape.position, half),
+        Core::Segment(half_height) => {
+            let ends = [
+                shape.position - Vec2::Y * half_height,
+                shape.position + Vec2::Y * half_height,
+            ];
+            ends.into_iter()
+                .filter_map(|center| ray_circle(origin, direction, center, shape.radius))
+                .chain(ray_box(
+                    origin,
+                    direction,
+                    shape.position,
+                    Vec2::new(shape.radius, half_height),
+                ))
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+        }
+    }
+}
+
+fn ray_box(origin: Vec2, direction: Vec2, center: Vec2, half: Vec2) -> Option<(f32, Vec2)> {
+    let (min, max) = (center - half, center + half);
+    let mut near = f32::NEG_INFINITY;
+    let mut far = f32::INFINITY;
+    let mut normal = -direction;
+    for axis in 0..2 {
+        if direction[axis].abs() < f32::EPSILON {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let a = (min[axis] - origin[axis]) / direction[axis];
+        let b = (max[axis] - origin[axis]) / direction[axis];
+        let (enter, exit) = (a.min(b), a.max(b));
+        if enter > near {
+            near = enter;
+            normal = Vec2::ZERO;
+            normal[axis] = -sign(direction[axis]);
+        }
+        far = far.min(exit);
+    }
+    if near > far || far < 0. {
+        return None;
+    }
+    if near < 0. {
+        return Some((0., -direction));
+    }
+    Some((near, normal))
+}
+
+fn ray_circle(origin: Vec2, direction: Vec2, center: Vec2, radius: f32) -> Option<(f32, Vec2)> {
+    let offset = origin - center;
+    let b = offset.dot(direction);
+    let c = offset.length_squared() - radius * radius;
+    if c <= 0. {
+        return Some((0., -direction));
+    }
+    if b > 0. {
+        return None;
+    }
+    let discriminant = b * b - c;
+    if discriminant < 0. {
+        return None;
+    }
+    let distance = -b - discriminant.sqrt();
+    let normal = (origin + direction * distance - center).normalize_or_zero();
+    Some((distance, normal))
+}
+
+fn sign(value: f32) -> f32 {
+    if value < 0. {
+        -1.
+    } else {
+        1.
+    }
+}
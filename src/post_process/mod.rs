@@ -0,0 +1,176 @@
+//! Post-processing for the 3D camera - palette quantisation, vignette, chromatic aberration and
+//! film grain in a single pass after tonemapping, alongside bevy's bloom. The strength of each
+//! effect comes from a `.post.yaml` profile, and the settings decide which are enabled.
+use bevy::{core_pipeline::bloom::BloomSettings, prelude::*, reflect::TypePath};
+use bevy_common_assets::yaml::YamlAssetPlugin;
+use serde::{Deserialize, Serialize};
+
+use crate::{assets::MainGameAssets, camera::MainCamera, settings::Settings};
+
+mod render;
+
+pub struct PostProcessPlugin;
+
+impl Plugin for PostProcessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            YamlAssetPlugin::<PostProcessProfile>::new(&["post.yaml"]),
+            render::PostProcessRenderPlugin,
+        ))
+        .add_systems(
+            Update,
+            (attach_post_process, apply_post_process)
+                .chain()
+                .run_if(resource_exists::<MainGameAssets>()),
+        );
+    }
+}
+
+/// The strength of each post-processing effect, when enabled in the settings.
+#[derive(Deserialize, TypePath, Asset, Clone, Copy, Debug, Default)]
+#[serde(default)]
+pub struct PostProcessProfile {
+    pub vignette: Vignette,
+    /// How far apart the red and blue channels are pulled at the edges of the screen, in UV space
+    pub chromatic_aberration: f32,
+    pub grain: f32,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct Vignette {
+    /// How dark the edges get, from 0 to 1
+    pub intensity: f32,
+    /// How far from the centre the darkening ends, where 1 is the corners
+    pub radius: f32,
+    /// How far before the radius the darkening starts
+    pub softness: f32,
+}
+
+impl Default for Vignette {
+    fn default() -> Self {
+        Self {
+            intensity: 0.,
+            radius: 1.,
+            softness: 0.5,
+        }
+    }
+}
+
+/// The post-processing applied by a 3D camera.
+#[derive(Component, Clone, Debug, Default)]
+pub struct PostProcess {
+    /// Quantises the image to the colours in this image's first row
+    pub palette: Option<Handle<Image>>,
+    pub vignette: Vignette,
+    pub chromatic_aberration: f32,
+    pub grain: f32,
+}
+
+impl PostProcess {
+    /// Whether there's anything for the post-process pass to do.
+    pub fn is_active(&self) -> bool {
+        self.palette.is_some()
+            || self.vignette.intensity > 0.
+            || self.chromatic_aberration > 0.
+            || self.grain > 0.
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BloomPreset {
+    Off,
+    Natural,
+    OldSchool,
+    ScreenBlur,
+}
+
+impl BloomPreset {
+    pub fn settings(&self) -> Option<BloomSettings> {
+        match self {
+            Self::Off => None,
+            Self::Natural => Some(BloomSettings::NATURAL),
+            Self::OldSchool => Some(BloomSettings::OLD_SCHOOL),
+            Self::ScreenBlur => Some(BloomSettings::SCREEN_BLUR),
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Off => Self::Natural,
+            Self::Natural => Self::OldSchool,
+            Self::OldSchool => Self::ScreenBlur,
+            Self::ScreenBlur => Self::Off,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Natural => "Natural",
+            Self::OldSchool => "Old School",
+            Self::ScreenBlur => "Screen Blur",
+        }
+    }
+}
+
+type UnprocessedCameraQuery<'w, 's> =
+    Query<'w, 's, Entity, (With<MainCamera>, With<Camera3d>, Without<PostProcess>)>;
+
+fn attach_post_process(mut commands: Commands, cameras: UnprocessedCameraQuery) {
+    for camera in cameras.iter() {
+        commands.entity(camera).insert(PostProcess::default());
+    }
+}
+
+fn apply_post_process(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    assets: Res<MainGameAssets>,
+    profiles: Res<Assets<PostProcessProfile>>,
+    mut events: EventReader<AssetEvent<PostProcessProfile>>,
+    mut cameras: Query<(Entity, &mut PostProcess, &mut Camera), With<MainCamera>>,
+) {
+    let reloaded = events.read().any(|e| {
+        e.is_loaded_with_dependencies(&assets.post_process) || e.is_modified(&assets.post_process)
+    });
+    let profile = profiles
+        .get(&assets.post_process)
+        .copied()
+        .unwrap_or_default();
+
+    for (entity, mut post_process, mut camera) in cameras.iter_mut() {
+        if !reloaded && !settings.is_changed() && !post_process.is_added() {
+            continue;
+        }
+        *post_process = PostProcess {
+            palette: settings.palette.then(|| assets.palette.clone()),
+            vignette: if settings.vignette {
+                profile.vignette
+            } else {
+                Vignette::default()
+            },
+            chromatic_aberration: if settings.chromatic_aberration {
+                profile.chromatic_aberration
+            } else {
+                0.
+            },
+            grain: if settings.film_grain {
+                profile.grain
+            } else {
+                0.
+            },
+        };
+
+        match settings.bloom.settings() {
+            Some(bloom) => {
+                camera.hdr = true;
+                commands.entity(entity).insert(bloom);
+            }
+            None => {
+                camera.hdr = false;
+                commands.entity(entity).remove::<BloomSettings>();
+            }
+        }
+    }
+}
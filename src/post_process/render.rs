@@ -0,0 +1,280 @@
+use bevy::{
+    core_pipeline::{core_3d, fullscreen_vertex_shader::fullscreen_shader_vertex_state},
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        globals::{GlobalsBuffer, GlobalsUniform},
+        render_asset::RenderAssets,
+        render_graph::{
+            NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner,
+        },
+        render_resource::*,
+        renderer::{RenderContext, RenderDevice},
+        texture::{BevyDefault, FallbackImage},
+        view::{ExtractedView, ViewTarget},
+        Render, RenderApp, RenderSet,
+    },
+};
+
+use super::PostProcess;
+
+const NODE_NAME: &str = "post_process";
+
+pub struct PostProcessRenderPlugin;
+
+impl Plugin for PostProcessRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ExtractComponentPlugin::<PostProcess>::default(),
+            UniformComponentPlugin::<PostProcessUniform>::default(),
+        ));
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .init_resource::<SpecializedRenderPipelines<PostProcessPipeline>>()
+            .add_systems(Render, prepare_pipelines.in_set(RenderSet::Prepare))
+            .add_render_graph_node::<ViewNodeRunner<PostProcessNode>>(
+                core_3d::graph::NAME,
+                NODE_NAME,
+            )
+            .add_render_graph_edges(
+                core_3d::graph::NAME,
+                &[
+                    core_3d::graph::node::TONEMAPPING,
+                    NODE_NAME,
+                    core_3d::graph::node::END_MAIN_PASS_POST_PROCESSING,
+                ],
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<PostProcessPipeline>();
+    }
+}
+
+impl ExtractComponent for PostProcess {
+    type Query = &'static Self;
+    type Filter = With<Camera3d>;
+    type Out = (PostProcessUniform, PostProcessPalette);
+
+    fn extract_component(item: QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        if !item.is_active() {
+            return None;
+        }
+        Some((
+            PostProcessUniform {
+                vignette_intensity: item.vignette.intensity,
+                vignette_radius: item.vignette.radius,
+                vignette_softness: item.vignette.softness.max(0.001),
+                chromatic_aberration: item.chromatic_aberration,
+                grain: item.grain,
+                ..default()
+            },
+            PostProcessPalette(item.palette.clone()),
+        ))
+    }
+}
+
+/// The parameters of the post-process pass - must match `PostProcessSettings` in the shader.
+#[derive(Component, ShaderType, Clone, Copy, Default)]
+pub struct PostProcessUniform {
+    vignette_intensity: f32,
+    vignette_radius: f32,
+    vignette_softness: f32,
+    chromatic_aberration: f32,
+    grain: f32,
+    // WebGL2 needs uniforms to be a multiple of 16 bytes
+    _padding_0: f32,
+    _padding_1: f32,
+    _padding_2: f32,
+}
+
+#[derive(Component)]
+pub struct PostProcessPalette(Option<Handle<Image>>);
+
+#[derive(Component)]
+struct PostProcessPipelineId(CachedRenderPipelineId);
+
+#[derive(Resource)]
+struct PostProcessPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for PostProcessPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let fragment = |binding, ty| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty,
+            count: None,
+        };
+        let texture = BindingType::Texture {
+            sample_type: TextureSampleType::Float { filterable: true },
+            view_dimension: TextureViewDimension::D2,
+            multisampled: false,
+        };
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("post_process_bind_group_layout"),
+            entries: &[
+                fragment(0, texture),
+                fragment(1, BindingType::Sampler(SamplerBindingType::Filtering)),
+                fragment(
+                    2,
+                    BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(PostProcessUniform::min_size()),
+                    },
+                ),
+                fragment(3, texture),
+                fragment(
+                    4,
+                    BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(GlobalsUniform::min_size()),
+                    },
+                ),
+            ],
+        });
+        let sampler = render_device.create_sampler(&SamplerDescriptor::default());
+        let shader = world
+            .resource::<AssetServer>()
+            .load("shaders/post_process.wgsl");
+
+        Self {
+            layout,
+            sampler,
+            shader,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for PostProcessPipeline {
+    /// Whether the view is HDR
+    type Key = bool;
+
+    fn specialize(&self, hdr: Self::Key) -> RenderPipelineDescriptor {
+        let format = if hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+        RenderPipelineDescriptor {
+            label: Some("post_process_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+fn prepare_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<PostProcessPipeline>>,
+    pipeline: Res<PostProcessPipeline>,
+    views: Query<(Entity, &ExtractedView), With<PostProcessUniform>>,
+) {
+    for (entity, view) in views.iter() {
+        let id = pipelines.specialize(&pipeline_cache, &pipeline, view.hdr);
+        commands.entity(entity).insert(PostProcessPipelineId(id));
+    }
+}
+
+#[derive(Default)]
+struct PostProcessNode;
+
+impl ViewNode for PostProcessNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static PostProcessPipelineId,
+        &'static DynamicUniformIndex<PostProcessUniform>,
+        &'static PostProcessPalette,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, pipeline_id, uniform_index, palette): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let post_process_pipeline = world.resource::<PostProcessPipeline>();
+        let Some(pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(pipeline_id.0)
+        else {
+            return Ok(());
+        };
+        let Some(settings) = world
+            .resource::<ComponentUniforms<PostProcessUniform>>()
+            .uniforms()
+            .binding()
+        else {
+            return Ok(());
+        };
+        let Some(globals) = world.resource::<GlobalsBuffer>().buffer.binding() else {
+            return Ok(());
+        };
+        // Without a palette the 1x1 fallback is bound, which the shader treats as disabled.
+        let palette = palette
+            .0
+            .as_ref()
+            .and_then(|handle| world.resource::<RenderAssets<Image>>().get(handle))
+            .unwrap_or(&world.resource::<FallbackImage>().d2);
+
+        let post_process = view_target.post_process_write();
+        let bind_group = render_context.render_device().create_bind_group(
+            "post_process_bind_group",
+            &post_process_pipeline.layout,
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &post_process_pipeline.sampler,
+                settings,
+                &palette.texture_view,
+                globals,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("post_process_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+        Ok(())
+    }
+}
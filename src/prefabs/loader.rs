@@ -0,0 +1,92 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, BoxedFuture, LoadContext},
+    prelude::*,
+    reflect::{TypePath, TypeRegistry, TypeRegistryArc},
+    scene::serde::SceneMapDeserializer,
+};
+use serde::{de::DeserializeSeed, Deserialize};
+
+/// An entity described by its reflected components, with optional children and a scene.
+#[derive(Asset, TypePath)]
+pub struct Prefab {
+    pub name: Option<String>,
+    /// Spawned as a child, usually a glTF scene like `models/crate.glb#Scene0`
+    pub scene: Option<Handle<Scene>>,
+    pub components: Vec<Box<dyn Reflect>>,
+    pub children: Vec<Prefab>,
+}
+
+#[derive(Deserialize)]
+struct PrefabFile {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    scene: Option<String>,
+    /// Component values keyed by their full type path, deserialized with the type registry
+    #[serde(default)]
+    components: serde_yaml::Value,
+    #[serde(default)]
+    children: Vec<PrefabFile>,
+}
+
+pub(super) struct PrefabLoader {
+    registry: TypeRegistryArc,
+}
+
+impl FromWorld for PrefabLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            registry: world.resource::<AppTypeRegistry>().0.clone(),
+        }
+    }
+}
+
+impl AssetLoader for PrefabLoader {
+    type Asset = Prefab;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let file: PrefabFile = serde_yaml::from_slice(&bytes)?;
+
+            let registry = self.registry.read();
+            build(file, &registry, load_context)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["prefab.yaml"]
+    }
+}
+
+fn build(
+    file: PrefabFile,
+    registry: &TypeRegistry,
+    load_context: &mut LoadContext,
+) -> Result<Prefab, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let components = if file.components.is_null() {
+        vec![]
+    } else {
+        SceneMapDeserializer { registry }.deserialize(file.components)?
+    };
+    let children = file
+        .children
+        .into_iter()
+        .map(|child| build(child, registry, load_context))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Prefab {
+        name: file.name,
+        scene: file.scene.map(|path| load_context.load(path)),
+        components,
+        children,
+    })
+}
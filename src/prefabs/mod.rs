@@ -0,0 +1,239 @@
+//! Prefabs - `.prefab.yaml` assets describing an entity as a list of reflected components, keyed by
+//! their type path like in `.scn.ron` scenes, with optional children and a scene.
+//!
+//! Prefabs listed in [`PrefabAssets`] can be spawned by name with [`SpawnPrefab`], and instances
+//! are rebuilt whenever their file changes.
+use std::any::TypeId;
+
+use bevy::{
+    ecs::system::Command,
+    pbr::{CascadeShadowConfig, Cascades, CascadesVisibleEntities, CubemapVisibleEntities},
+    prelude::*,
+    reflect::TypeRegistry,
+    render::{
+        primitives::{CascadesFrusta, CubemapFrusta, Frustum},
+        view::VisibleEntities,
+    },
+};
+
+use crate::{assets::PrefabAssets, in_game::InGame};
+
+mod loader;
+
+pub use loader::Prefab;
+
+pub struct PrefabPlugin;
+
+impl Plugin for PrefabPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Prefab>()
+            .init_asset_loader::<loader::PrefabLoader>()
+            .add_systems(Update, reload_prefabs);
+    }
+}
+
+/// An entity spawned from a prefab.
+#[derive(Component)]
+pub struct PrefabInstance {
+    pub handle: Handle<Prefab>,
+    /// The components the prefab added, so they can be removed if they are taken out of the file
+    applied: Vec<TypeId>,
+}
+
+/// An entity spawned as part of a prefab instance, replaced when the prefab is rebuilt.
+#[derive(Component)]
+struct PrefabPart;
+
+/// Spawns the prefab with the given name, under the [`InGame`] root unless given another parent.
+pub struct SpawnPrefab {
+    pub name: String,
+    /// Replaced by the prefab's own [`Transform`] if it has one
+    pub transform: Transform,
+    pub parent: Option<Entity>,
+}
+
+impl SpawnPrefab {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            transform: Transform::default(),
+            parent: None,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn with_parent(mut self, parent: Entity) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+}
+
+impl Command for SpawnPrefab {
+    fn apply(self, world: &mut World) {
+        let Some(handle) = world
+            .get_resource::<PrefabAssets>()
+            .and_then(|prefabs| prefabs.get(&self.name))
+            .cloned()
+        else {
+            warn!("No prefab named {}", self.name);
+            return;
+        };
+        let parent = self.parent.or_else(|| {
+            world
+                .query_filtered::<Entity, With<InGame>>()
+                .iter(world)
+                .next()
+        });
+
+        let entity = world
+            .spawn((
+                Name::new(self.name),
+                SpatialBundle::from_transform(self.transform),
+                PrefabInstance {
+                    handle,
+                    applied: vec![],
+                },
+            ))
+            .id();
+        if let Some(parent) = parent {
+            world.entity_mut(parent).add_child(entity);
+        }
+        ApplyPrefab(entity).apply(world);
+    }
+}
+
+/// (Re)builds a prefab instance from its asset.
+struct ApplyPrefab(Entity);
+
+impl Command for ApplyPrefab {
+    fn apply(self, world: &mut World) {
+        let entity = self.0;
+        let Some(instance) = world.get::<PrefabInstance>(entity) else {
+            return;
+        };
+        let handle = instance.handle.clone();
+        let previous = instance.applied.clone();
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        world.resource_scope(|world, prefabs: Mut<Assets<Prefab>>| {
+            let Some(prefab) = prefabs.get(&handle) else {
+                return;
+            };
+
+            let parts = world
+                .get::<Children>(entity)
+                .map(|children| {
+                    children
+                        .iter()
+                        .copied()
+                        .filter(|child| world.get::<PrefabPart>(*child).is_some())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            for part in parts {
+                world.entity_mut(part).despawn_recursive();
+            }
+
+            let applied = spawn_node(world, entity, prefab, &registry);
+            for type_id in previous.iter().filter(|id| !applied.contains(id)) {
+                if let Some(component) = registry.get_type_data::<ReflectComponent>(*type_id) {
+                    component.remove(&mut world.entity_mut(entity));
+                }
+            }
+            if let Some(mut instance) = world.get_mut::<PrefabInstance>(entity) {
+                instance.applied = applied;
+            }
+        });
+    }
+}
+
+/// Adds a prefab's components, scene and children to an entity, returning the component types added.
+fn spawn_node(
+    world: &mut World,
+    entity: Entity,
+    prefab: &Prefab,
+    registry: &TypeRegistry,
+) -> Vec<TypeId> {
+    let mut applied = vec![];
+    for component in prefab.components.iter() {
+        let Some(info) = component.get_represented_type_info() else {
+            continue;
+        };
+        let Some(reflect) = registry.get_type_data::<ReflectComponent>(info.type_id()) else {
+            warn!("{} isn't a registered component", info.type_path());
+            continue;
+        };
+        reflect.apply_or_insert(&mut world.entity_mut(entity), component.as_ref());
+        applied.push(info.type_id());
+    }
+    insert_light_bundle(&mut world.entity_mut(entity));
+    if let Some(name) = &prefab.name {
+        world.entity_mut(entity).insert(Name::new(name.clone()));
+    }
+
+    if let Some(scene) = &prefab.scene {
+        let part = world
+            .spawn((
+                PrefabPart,
+                SceneBundle {
+                    scene: scene.clone(),
+                    ..default()
+                },
+            ))
+            .id();
+        world.entity_mut(entity).add_child(part);
+    }
+    for child in prefab.children.iter() {
+        let part = world.spawn((PrefabPart, SpatialBundle::default())).id();
+        world.entity_mut(entity).add_child(part);
+        spawn_node(world, part, child, registry);
+    }
+    applied
+}
+
+/// Lights only render with the rest of their bundle, which a prefab can't reflect,
+/// so fill it in for any light the prefab added.
+fn insert_light_bundle(entity: &mut EntityWorldMut) {
+    if entity.contains::<PointLight>() && !entity.contains::<CubemapFrusta>() {
+        entity.insert((CubemapFrusta::default(), CubemapVisibleEntities::default()));
+    }
+    if entity.contains::<SpotLight>() && !entity.contains::<Frustum>() {
+        entity.insert((Frustum::default(), VisibleEntities::default()));
+    }
+    if entity.contains::<DirectionalLight>() && !entity.contains::<CascadesFrusta>() {
+        entity.insert((
+            CascadesFrusta::default(),
+            Cascades::default(),
+            CascadeShadowConfig::default(),
+            CascadesVisibleEntities::default(),
+        ));
+    }
+}
+
+fn reload_prefabs(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<Prefab>>,
+    instances: Query<(Entity, &PrefabInstance)>,
+) {
+    let changed = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if changed.is_empty() {
+        return;
+    }
+    for (entity, instance) in instances.iter() {
+        if changed.contains(&instance.handle.id()) {
+            commands.add(ApplyPrefab(entity));
+        }
+    }
+}
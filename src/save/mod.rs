@@ -0,0 +1,330 @@
+use std::{any::TypeId, fmt::Display};
+
+use bevy::{
+    ecs::system::Command,
+    prelude::*,
+    reflect::GetTypeRegistration,
+    scene::{serde::SceneDeserializer, SceneFilter},
+    utils::HashMap,
+};
+use dexterous_developer::{
+    dexterous_developer_setup, ReloadableApp, ReloadableAppContents, ReloadableElementsSetup,
+};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+
+use crate::{
+    app_state::AppState,
+    in_game::{game_state::GameState, InGame},
+    levels::CurrentLevel,
+    ui::toasts::Notification,
+};
+
+use self::quicksave::QuickSavePlugin;
+
+pub mod quicksave;
+pub mod storage;
+
+/// Bump this whenever the saved components or resources change in an incompatible way.
+pub const SAVE_VERSION: u32 = 1;
+pub const SAVE_SLOTS: usize = 3;
+
+const LAST_SLOT_KEY: &str = "last_slot";
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(QuickSavePlugin)
+            .init_resource::<SaveRegistry>()
+            .init_resource::<ActiveSaveSlot>()
+            .save_component::<Persistent>()
+            .setup_reloadable_elements::<reloadable>();
+    }
+}
+
+#[dexterous_developer_setup(save)]
+fn reloadable(app: &mut ReloadableAppContents) {
+    app.add_systems(
+        Update,
+        apply_pending_load
+            .run_if(in_state(GameState::InGame))
+            .run_if(resource_exists::<PendingLoad>()),
+    );
+}
+
+/// Registers the components & resources that get written to save files.
+///
+/// Saved types need to reflect `Component` or `Resource` respectively,
+/// so they can be re-created when loading.
+pub trait SaveAppExt {
+    fn save_component<C: Component + GetTypeRegistration>(&mut self) -> &mut Self;
+    #[allow(dead_code)]
+    fn save_resource<R: Resource + GetTypeRegistration>(&mut self) -> &mut Self;
+}
+
+impl SaveAppExt for App {
+    fn save_component<C: Component + GetTypeRegistration>(&mut self) -> &mut Self {
+        self.register_type::<C>();
+        self.world
+            .get_resource_or_insert_with(SaveRegistry::default)
+            .components
+            .push(TypeId::of::<C>());
+        self
+    }
+
+    fn save_resource<R: Resource + GetTypeRegistration>(&mut self) -> &mut Self {
+        self.register_type::<R>();
+        self.world
+            .get_resource_or_insert_with(SaveRegistry::default)
+            .resources
+            .push(TypeId::of::<R>());
+        self
+    }
+}
+
+#[derive(Resource, Default, Debug, Clone)]
+pub struct SaveRegistry {
+    components: Vec<TypeId>,
+    resources: Vec<TypeId>,
+}
+
+/// Marks an entity whose saved components get written to the save file.
+///
+/// The hierarchy isn't saved, and loaded entities are added directly under the [`InGame`] root,
+/// so only put it on children of the root - nested ones are skipped with a warning.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct Persistent;
+
+/// The slot the current run gets saved into.
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct ActiveSaveSlot(pub usize);
+
+/// A slot to load once the game has been set up.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct PendingLoad(pub usize);
+
+#[derive(Debug, Clone)]
+pub struct SaveError(String);
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl SaveError {
+    pub fn new(message: impl Display) -> Self {
+        Self(message.to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    /// The path of the level being played when saving
+    #[serde(default)]
+    level: Option<String>,
+    scene: String,
+}
+
+/// Writes the persistent entities and saved resources into a slot.
+pub struct SaveGame {
+    pub slot: usize,
+}
+
+impl Command for SaveGame {
+    fn apply(self, world: &mut World) {
+        match save(world, self.slot) {
+            Ok(_) => world.send_event(Notification::info("Game Saved")),
+            Err(e) => {
+                error!("Couldn't save to slot {}: {e}", self.slot);
+                world.send_event(Notification::error(format!("Couldn't save: {e}")));
+            }
+        }
+    }
+}
+
+/// Replaces the persistent entities and saved resources with the contents of a slot.
+pub struct LoadGame {
+    pub slot: usize,
+}
+
+impl Command for LoadGame {
+    fn apply(self, world: &mut World) {
+        match load(world, self.slot) {
+            Ok(_) => world.send_event(Notification::info("Game Loaded")),
+            Err(e) => {
+                error!("Couldn't load slot {}: {e}", self.slot);
+                world.send_event(Notification::error(format!("Couldn't load: {e}")));
+            }
+        }
+    }
+}
+
+pub fn has_save(slot: usize) -> bool {
+    matches!(storage::read(&slot_key(slot)), Ok(Some(_)))
+}
+
+/// The most recently saved slot, if it still exists.
+pub fn last_slot() -> Option<usize> {
+    let slot = storage::read(LAST_SLOT_KEY).ok()??.trim().parse().ok()?;
+    has_save(slot).then_some(slot)
+}
+
+/// Removes the save in a slot, leaving it free for a new game.
+pub fn delete_save(slot: usize) -> Result<(), SaveError> {
+    storage::delete(&slot_key(slot))
+}
+
+pub fn first_free_slot() -> Option<usize> {
+    (0..SAVE_SLOTS).find(|slot| !has_save(*slot))
+}
+
+/// Starts the game, loading the given slot once it's running.
+pub fn continue_game(commands: &mut Commands, slot: usize) {
+    let level = read_save(slot).ok().and_then(|file| file.level);
+    commands.insert_resource(level.map(CurrentLevel::new).unwrap_or_default());
+    commands.insert_resource(ActiveSaveSlot(slot));
+    commands.insert_resource(PendingLoad(slot));
+    commands.insert_resource(NextState(Some(AppState::InGame)));
+}
+
+/// Starts a fresh game, that will be saved into the given slot.
+pub fn new_game(commands: &mut Commands, slot: usize) {
+    new_game_on_level(commands, slot, CurrentLevel::default());
+}
+
+/// Starts a fresh game on a chosen level, that will be saved into the given slot.
+pub fn new_game_on_level(commands: &mut Commands, slot: usize, level: CurrentLevel) {
+    commands.insert_resource(level);
+    commands.insert_resource(ActiveSaveSlot(slot));
+    commands.insert_resource(NextState(Some(AppState::InGame)));
+}
+
+fn slot_key(slot: usize) -> String {
+    format!("save_slot_{slot}")
+}
+
+fn apply_pending_load(mut commands: Commands, pending: Res<PendingLoad>) {
+    commands.add(LoadGame { slot: pending.0 });
+    commands.remove_resource::<PendingLoad>();
+}
+
+fn save(world: &mut World, slot: usize) -> Result<(), SaveError> {
+    let registry = world.resource::<SaveRegistry>().clone();
+    let component_filter = registry
+        .components
+        .iter()
+        .fold(SceneFilter::deny_all(), |filter, id| {
+            filter.allow_by_id(*id)
+        });
+    let resource_filter = registry
+        .resources
+        .iter()
+        .fold(SceneFilter::deny_all(), |filter, id| {
+            filter.allow_by_id(*id)
+        });
+
+    let roots = world
+        .query_filtered::<Entity, With<InGame>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    let (entities, nested): (Vec<_>, Vec<_>) = world
+        .query_filtered::<(Entity, Option<&Parent>), With<Persistent>>()
+        .iter(world)
+        .partition(|(_, parent)| match parent {
+            Some(parent) => roots.contains(&parent.get()),
+            None => true,
+        });
+    for (entity, _) in nested {
+        warn!("Not saving {entity:?} - persistent entities need to be children of the InGame root");
+    }
+    let entities = entities.into_iter().map(|(entity, _)| entity);
+
+    let scene = DynamicSceneBuilder::from_world(world)
+        .with_filter(component_filter)
+        .with_resource_filter(resource_filter)
+        .extract_entities(entities)
+        .extract_resources()
+        .build();
+
+    let scene = scene
+        .serialize_ron(world.resource::<AppTypeRegistry>())
+        .map_err(SaveError::new)?;
+
+    let file = ron::ser::to_string_pretty(
+        &SaveFile {
+            version: SAVE_VERSION,
+            level: world
+                .get_resource::<CurrentLevel>()
+                .map(|level| level.path.clone()),
+            scene,
+        },
+        Default::default(),
+    )
+    .map_err(SaveError::new)?;
+
+    storage::write(&slot_key(slot), &file)?;
+    storage::write(LAST_SLOT_KEY, &slot.to_string())
+}
+
+fn read_save(slot: usize) -> Result<SaveFile, SaveError> {
+    let Some(contents) = storage::read(&slot_key(slot))? else {
+        return Err(SaveError::new(format!("Slot {} is empty", slot + 1)));
+    };
+    let file: SaveFile = ron::from_str(&contents).map_err(SaveError::new)?;
+    if file.version != SAVE_VERSION {
+        return Err(SaveError::new(format!(
+            "Save version {} isn't supported - expected {SAVE_VERSION}",
+            file.version
+        )));
+    }
+    Ok(file)
+}
+
+fn load(world: &mut World, slot: usize) -> Result<(), SaveError> {
+    let file = read_save(slot)?;
+
+    let scene = {
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let mut deserializer =
+            ron::de::Deserializer::from_str(&file.scene).map_err(SaveError::new)?;
+        SceneDeserializer {
+            type_registry: &type_registry,
+        }
+        .deserialize(&mut deserializer)
+        .map_err(SaveError::new)?
+    };
+
+    let existing = world
+        .query_filtered::<Entity, With<Persistent>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for entity in existing {
+        if let Some(entity) = world.get_entity_mut(entity) {
+            entity.despawn_recursive();
+        }
+    }
+
+    let mut entity_map = HashMap::default();
+    scene
+        .write_to_world(world, &mut entity_map)
+        .map_err(SaveError::new)?;
+
+    let root = world
+        .query_filtered::<Entity, With<InGame>>()
+        .iter(world)
+        .next();
+    if let Some(root) = root {
+        for entity in entity_map.into_values() {
+            if world.get::<Parent>(entity).is_none() {
+                world.entity_mut(root).add_child(entity);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+//! Debug quick saves - an in-memory snapshot of everything under the [`InGame`] root,
+//! bound to F5 (save) & F9 (load) in debug builds.
+use bevy::{
+    ecs::system::Command,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use dexterous_developer::{
+    dexterous_developer_setup, ReloadableApp, ReloadableAppContents, ReloadableElementsSetup,
+};
+
+use crate::{
+    app_state::AppState,
+    in_game::{game_state::GameState, InGame},
+    ui::toasts::Notification,
+};
+
+pub struct QuickSavePlugin;
+
+impl Plugin for QuickSavePlugin {
+    fn build(&self, app: &mut App) {
+        app.setup_reloadable_elements::<reloadable>();
+    }
+}
+
+#[dexterous_developer_setup(quicksave)]
+fn reloadable(app: &mut ReloadableAppContents) {
+    if cfg!(debug_assertions) {
+        app.add_systems(
+            Update,
+            process_keyboard_input.run_if(in_state(AppState::InGame)),
+        )
+        .add_systems(OnExit(AppState::InGame), clear_snapshot);
+    }
+}
+
+#[derive(Resource)]
+pub struct QuickSaveSnapshot {
+    scene: DynamicScene,
+    game_state: GameState,
+}
+
+/// Captures every reflected component on the entities under the [`InGame`] root.
+///
+/// Components that aren't registered, or don't reflect `Component`, can't be captured -
+/// they're logged when saving, and will be missing after a [`QuickLoad`].
+pub struct QuickSave;
+
+impl Command for QuickSave {
+    fn apply(self, world: &mut World) {
+        let mut entities = world
+            .query_filtered::<Entity, With<InGame>>()
+            .iter(world)
+            .collect::<Vec<_>>();
+        let mut index = 0;
+        while let Some(entity) = entities.get(index).copied() {
+            if let Some(children) = world.get::<Children>(entity) {
+                entities.extend(children.iter().copied());
+            }
+            index += 1;
+        }
+
+        let skipped = unreflected_components(world, &entities);
+        if !skipped.is_empty() {
+            warn!(
+                "Quick save skipped components that don't reflect Component: {}",
+                skipped.join(", ")
+            );
+        }
+
+        let scene = DynamicSceneBuilder::from_world(world)
+            .extract_entities(entities.into_iter())
+            .build();
+        let game_state = *world.resource::<State<GameState>>().get();
+
+        world.insert_resource(QuickSaveSnapshot { scene, game_state });
+        world.send_event(Notification::info("Quick Saved"));
+    }
+}
+
+/// Replaces everything under the [`InGame`] root with the last [`QuickSave`].
+pub struct QuickLoad;
+
+impl Command for QuickLoad {
+    fn apply(self, world: &mut World) {
+        if !world.contains_resource::<QuickSaveSnapshot>() {
+            world.send_event(Notification::warning("No Quick Save Available"));
+            return;
+        }
+
+        let roots = world
+            .query_filtered::<Entity, With<InGame>>()
+            .iter(world)
+            .collect::<Vec<_>>();
+        for root in roots {
+            world.entity_mut(root).despawn_recursive();
+        }
+
+        let result = world.resource_scope(|world, snapshot: Mut<QuickSaveSnapshot>| {
+            world.insert_resource(NextState(Some(snapshot.game_state)));
+            snapshot
+                .scene
+                .write_to_world(world, &mut HashMap::default())
+        });
+
+        match result {
+            Ok(_) => world.send_event(Notification::info("Quick Loaded")),
+            Err(e) => {
+                error!("Couldn't quick load: {e}");
+                world.send_event(Notification::error(format!("Couldn't quick load: {e}")));
+            }
+        }
+    }
+}
+
+/// The names of the components on `entities` that the scene builder will leave out.
+fn unreflected_components(world: &World, entities: &[Entity]) -> Vec<String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut skipped = HashSet::default();
+    for entity in entities.iter() {
+        let Some(entity) = world.get_entity(*entity) else {
+            continue;
+        };
+        for id in entity.archetype().components() {
+            let Some(info) = world.components().get_info(id) else {
+                continue;
+            };
+            let reflected = info
+                .type_id()
+                .and_then(|type_id| registry.get(type_id))
+                .is_some_and(|registration| registration.data::<ReflectComponent>().is_some());
+            if !reflected {
+                skipped.insert(info.name().to_string());
+            }
+        }
+    }
+    let mut skipped = skipped.into_iter().collect::<Vec<_>>();
+    skipped.sort();
+    skipped
+}
+
+fn clear_snapshot(mut commands: Commands) {
+    commands.remove_resource::<QuickSaveSnapshot>();
+}
+
+fn process_keyboard_input(mut commands: Commands, keys: Res<Input<KeyCode>>) {
+    if keys.just_pressed(KeyCode::F5) {
+        commands.add(QuickSave);
+    }
+    if keys.just_pressed(KeyCode::F9) {
+        commands.add(QuickLoad);
+    }
+}
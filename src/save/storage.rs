@@ -0,0 +1,76 @@
+//! Key-value storage for persisted data - files in the `saves` directory on native,
+//! and local storage on the web.
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use std::path::PathBuf;
+
+    use crate::save::SaveError;
+
+    const SAVE_DIRECTORY: &str = "saves";
+
+    fn path(key: &str) -> PathBuf {
+        PathBuf::from(SAVE_DIRECTORY).join(format!("{key}.ron"))
+    }
+
+    pub fn read(key: &str) -> Result<Option<String>, SaveError> {
+        let path = path(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        std::fs::read_to_string(path)
+            .map(Some)
+            .map_err(SaveError::new)
+    }
+
+    pub fn write(key: &str, contents: &str) -> Result<(), SaveError> {
+        std::fs::create_dir_all(SAVE_DIRECTORY).map_err(SaveError::new)?;
+        std::fs::write(path(key), contents).map_err(SaveError::new)
+    }
+
+    pub fn delete(key: &str) -> Result<(), SaveError> {
+        let path = path(key);
+        if !path.exists() {
+            return Ok(());
+        }
+        std::fs::remove_file(path).map_err(SaveError::new)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use crate::save::SaveError;
+
+    const KEY_PREFIX: &str = "game-template";
+
+    fn storage() -> Result<web_sys::Storage, SaveError> {
+        web_sys::window()
+            .ok_or_else(|| SaveError::new("No window available"))?
+            .local_storage()
+            .map_err(|e| SaveError::new(format!("{e:?}")))?
+            .ok_or_else(|| SaveError::new("Local storage is unavailable"))
+    }
+
+    fn key(key: &str) -> String {
+        format!("{KEY_PREFIX}/{key}")
+    }
+
+    pub fn read(key: &str) -> Result<Option<String>, SaveError> {
+        storage()?
+            .get_item(&self::key(key))
+            .map_err(|e| SaveError::new(format!("{e:?}")))
+    }
+
+    pub fn write(key: &str, contents: &str) -> Result<(), SaveError> {
+        storage()?
+            .set_item(&self::key(key), contents)
+            .map_err(|e| SaveError::new(format!("{e:?}")))
+    }
+
+    pub fn delete(key: &str) -> Result<(), SaveError> {
+        storage()?
+            .remove_item(&self::key(key))
+            .map_err(|e| SaveError::new(format!("{e:?}")))
+    }
+}
+
+pub use backend::{delete, read, write};
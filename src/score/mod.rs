@@ -0,0 +1,222 @@
+//! The score and time of the current run, and a table of the best runs on each level.
+//!
+//! Both reset whenever a level is (re)loaded, and only change while [`GameState::InGame`].
+//! The completed and game over screens show them with [`score_summary`], offer to put a good
+//! enough score in the [`HighScores`] with [`name_entry`], and list the level's best runs with
+//! [`high_score_table`].
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_ui_dsl::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    health::Died,
+    in_game::{
+        game_state::{GameState, PauseState},
+        hud::{HudAppExt, HudValue},
+    },
+    save::storage,
+    ui::{classes::*, intermediary_node_bundles::*},
+};
+
+mod name_entry;
+mod table;
+
+pub use name_entry::name_entry;
+pub use table::high_score_table;
+
+const HIGH_SCORES_KEY: &str = "high_scores";
+/// How many runs each level's table keeps.
+pub const HIGH_SCORE_ENTRIES: usize = 10;
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Score>()
+            .init_resource::<RunTimer>()
+            .insert_resource(load_high_scores())
+            .add_event::<AddScore>()
+            .register_type::<Points>()
+            .bind_hud_resource::<Score>()
+            .bind_hud_resource::<RunTimer>()
+            .add_systems(OnEnter(GameState::LoadingLevel), reset_run)
+            .add_systems(
+                Update,
+                (
+                    (award_points, add_score).chain(),
+                    tick_run_timer.run_if(in_state(PauseState::None)),
+                )
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                save_high_scores.run_if(resource_changed::<HighScores>()),
+            )
+            .add_plugins((name_entry::NameEntryPlugin, table::HighScoreTablePlugin));
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Deref, DerefMut)]
+pub struct Score(pub u32);
+
+impl Score {
+    #[allow(dead_code)]
+    pub fn hud_value(&self) -> HudValue {
+        HudValue::new(self.0 as f32, 0.)
+    }
+}
+
+/// How long the current level has been played, not counting time spent paused.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct RunTimer {
+    pub elapsed: Duration,
+}
+
+impl RunTimer {
+    #[allow(dead_code)]
+    pub fn hud_value(&self) -> HudValue {
+        HudValue::new(self.elapsed.as_secs_f32(), 0.)
+    }
+}
+
+/// Adds to the [`Score`], as long as the level is being played.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct AddScore(pub u32);
+
+/// Points scored when this entity dies.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct Points(pub u32);
+
+/// The best runs on each level, by level path, persisted between sessions.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct HighScores {
+    pub levels: HashMap<String, Vec<HighScore>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HighScore {
+    pub name: String,
+    pub score: u32,
+    pub time: f32,
+}
+
+impl HighScores {
+    /// The table for a level, from the best run down.
+    pub fn table(&self, level: &str) -> &[HighScore] {
+        self.levels
+            .get(level)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn best(&self, level: &str) -> Option<u32> {
+        self.table(level).first().map(|entry| entry.score)
+    }
+
+    /// Whether a score would make it into a level's table.
+    pub fn qualifies(&self, level: &str, score: u32) -> bool {
+        let table = self.table(level);
+        score > 0
+            && (table.len() < HIGH_SCORE_ENTRIES
+                || table.last().is_some_and(|entry| score > entry.score))
+    }
+
+    pub fn insert(&mut self, level: &str, entry: HighScore) {
+        let table = self.levels.entry(level.to_string()).or_default();
+        let position = table
+            .iter()
+            .position(|existing| entry.score > existing.score)
+            .unwrap_or(table.len());
+        table.insert(position, entry);
+        table.truncate(HIGH_SCORE_ENTRIES);
+    }
+}
+
+/// Formats a time as minutes, seconds and hundredths.
+pub fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs_f32();
+    let minutes = (seconds / 60.).floor() as u32;
+    format!("{minutes:02}:{:05.2}", seconds % 60.)
+}
+
+/// Spawns the score, time and best score of the finished run.
+pub fn score_summary(
+    score: Score,
+    timer: RunTimer,
+    best: Option<u32>,
+    parent: &mut UiChildBuilder,
+) {
+    text(
+        format!("Score: {}", score.0),
+        primary_box_item.nb(),
+        standard_text,
+        parent,
+    );
+    text(
+        format!("Time: {}", format_time(timer.elapsed)),
+        primary_box_item.nb(),
+        standard_text,
+        parent,
+    );
+    let best = match best {
+        Some(best) if score.0 > best => "New best score!".to_string(),
+        Some(best) => format!("Best: {best}"),
+        None => "First run!".to_string(),
+    };
+    text(best, primary_box_item.nb(), standard_text, parent);
+}
+
+fn reset_run(mut score: ResMut<Score>, mut timer: ResMut<RunTimer>) {
+    *score = Score::default();
+    *timer = RunTimer::default();
+}
+
+fn tick_run_timer(time: Res<Time>, mut timer: ResMut<RunTimer>) {
+    timer.elapsed += time.delta();
+}
+
+fn award_points(
+    mut died: EventReader<Died>,
+    points: Query<&Points>,
+    mut events: EventWriter<AddScore>,
+) {
+    for event in died.read() {
+        if let Ok(points) = points.get(event.entity) {
+            events.send(AddScore(points.0));
+        }
+    }
+}
+
+fn add_score(mut events: EventReader<AddScore>, mut score: ResMut<Score>) {
+    let added: u32 = events.read().map(|event| event.0).sum();
+    if added > 0 {
+        score.0 += added;
+    }
+}
+
+fn load_high_scores() -> HighScores {
+    match storage::read(HIGH_SCORES_KEY) {
+        Ok(Some(contents)) => ron::from_str(&contents).unwrap_or_else(|e| {
+            warn!("Couldn't parse high scores, starting over: {e}");
+            HighScores::default()
+        }),
+        Ok(None) => HighScores::default(),
+        Err(e) => {
+            warn!("Couldn't read high scores, starting over: {e}");
+            HighScores::default()
+        }
+    }
+}
+
+fn save_high_scores(high_scores: Res<HighScores>) {
+    let result = ron::ser::to_string_pretty(high_scores.as_ref(), Default::default())
+        .map_err(|e| e.to_string())
+        .and_then(|contents| storage::write(HIGH_SCORES_KEY, &contents).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        error!("Couldn't save high scores: {e}");
+    }
+}
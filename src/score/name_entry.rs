@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+use bevy_ui_dsl::*;
+use bevy_ui_navigation::systems::InputMapping;
+
+use crate::ui::{
+    buttons::{focus_text_button, focused_button_activated},
+    classes::*,
+    intermediary_node_bundles::*,
+};
+
+use super::{HighScore, HighScores, RunTimer, Score};
+
+const MAX_NAME_LENGTH: usize = 12;
+
+pub(super) struct NameEntryPlugin;
+
+impl Plugin for NameEntryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                type_name,
+                focused_button_activated.pipe(save_name),
+                toggle_keyboard_navigation,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// A text field for the name a high score is saved under, saved by pressing Enter or the button
+/// spawned after it.
+#[derive(Component, Debug, Clone)]
+pub struct NameEntry {
+    level: String,
+    score: u32,
+    time: f32,
+    name: String,
+}
+
+/// Spawns a [`NameEntry`] for a run on the given level, followed by its save button.
+/// Call it before spawning the screen's other buttons, so the save button starts out focused.
+pub fn name_entry(
+    level: impl Into<String>,
+    score: Score,
+    timer: RunTimer,
+    parent: &mut UiChildBuilder,
+) -> Entity {
+    let mut style = TextStyle::default();
+    standard_text(parent.assets(), &mut style);
+    let mut bundle = TextBundle::from_section(prompt(""), style);
+    primary_box_item(&mut bundle);
+    let entry = parent
+        .spawn((
+            bundle,
+            NameEntry {
+                level: level.into(),
+                score: score.0,
+                time: timer.elapsed.as_secs_f32(),
+                name: String::new(),
+            },
+        ))
+        .id();
+    focus_text_button(
+        "Save Score",
+        (c_button.nb(), primary_box_item.nb()),
+        apply_button_state,
+        button_text,
+        parent,
+    );
+    entry
+}
+
+fn prompt(name: &str) -> String {
+    format!("New high score! Name: {name}_")
+}
+
+fn type_name(
+    mut characters: EventReader<ReceivedCharacter>,
+    keys: Res<Input<KeyCode>>,
+    mut entries: Query<(&mut NameEntry, &mut Text)>,
+) {
+    let typed = characters
+        .read()
+        .map(|event| event.char)
+        .collect::<Vec<_>>();
+    let erase = keys.just_pressed(KeyCode::Back);
+    if typed.is_empty() && !erase {
+        return;
+    }
+    for (mut entry, mut text) in entries.iter_mut() {
+        for c in typed.iter() {
+            if (c.is_alphanumeric() || *c == ' ') && entry.name.chars().count() < MAX_NAME_LENGTH {
+                entry.name.push(*c);
+            }
+        }
+        if erase {
+            entry.name.pop();
+        }
+        text.sections[0].value = prompt(&entry.name);
+    }
+}
+
+fn save_name(
+    In(focused): In<Option<Entity>>,
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    mut entries: Query<(Entity, &NameEntry, &Parent, &mut Text)>,
+    children: Query<&Children>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    let submitted = keys.just_pressed(KeyCode::Return);
+    if focused.is_none() && !submitted {
+        return;
+    }
+    for (entity, entry, parent, mut text) in entries.iter_mut() {
+        let save_button = children
+            .get(parent.get())
+            .ok()
+            .and_then(|siblings| siblings.iter().skip_while(|e| **e != entity).nth(1));
+        let pressed = focused.is_some() && save_button == focused.as_ref();
+        let name = entry.name.trim();
+        if !(submitted || pressed) || name.is_empty() {
+            continue;
+        }
+        high_scores.insert(
+            &entry.level,
+            HighScore {
+                name: name.to_string(),
+                score: entry.score,
+                time: entry.time,
+            },
+        );
+        text.sections[0].value = format!("Saved as {name}");
+        commands.entity(entity).remove::<NameEntry>();
+    }
+}
+
+/// Keyboard navigation would steal letters, so it's off while a name is being typed.
+fn toggle_keyboard_navigation(
+    entries: Query<(), With<NameEntry>>,
+    mut mapping: ResMut<InputMapping>,
+) {
+    let enabled = entries.is_empty();
+    if mapping.keyboard_navigation != enabled {
+        mapping.keyboard_navigation = enabled;
+    }
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ui_dsl::*;
+
+use crate::ui::classes::*;
+
+use super::{format_time, HighScore, HighScores};
+
+/// How many of a level's best runs a table shows.
+const SHOWN_ENTRIES: usize = 5;
+
+pub(super) struct HighScoreTablePlugin;
+
+impl Plugin for HighScoreTablePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            update_tables.run_if(resource_changed::<HighScores>()),
+        );
+    }
+}
+
+/// Shows the best runs on a level, updated as new high scores are saved.
+#[derive(Component, Debug, Clone)]
+pub struct HighScoreTable(pub String);
+
+#[derive(Component)]
+struct HighScoreRow(usize);
+
+/// Spawns a [`HighScoreTable`] for the given level, with the name, score and time of each run.
+pub fn high_score_table(
+    level: impl Into<String>,
+    high_scores: &HighScores,
+    parent: &mut UiChildBuilder,
+) -> Entity {
+    let level = level.into();
+    let entries = high_scores.table(&level);
+    let mut bundle = NodeBundle::default();
+    high_score_list(&mut bundle);
+    primary_box_item(&mut bundle);
+    parent
+        .spawn((bundle, HighScoreTable(level)))
+        .with_children(|p| {
+            text("High Scores", (), standard_text, p);
+            for index in 0..SHOWN_ENTRIES {
+                let mut style = TextStyle::default();
+                high_score_text(p.assets(), &mut style);
+                let mut row = TextBundle::from_section(row_text(entries, index), style);
+                row.style.display = row_display(entries, index);
+                p.spawn((row, HighScoreRow(index)));
+            }
+        })
+        .id()
+}
+
+fn update_tables(
+    high_scores: Res<HighScores>,
+    tables: Query<(&HighScoreTable, &Children)>,
+    mut rows: Query<(&HighScoreRow, &mut Text, &mut Style)>,
+) {
+    for (table, children) in tables.iter() {
+        let entries = high_scores.table(&table.0);
+        for child in children.iter() {
+            if let Ok((row, mut text, mut style)) = rows.get_mut(*child) {
+                text.sections[0].value = row_text(entries, row.0);
+                style.display = row_display(entries, row.0);
+            }
+        }
+    }
+}
+
+fn row_text(entries: &[HighScore], index: usize) -> String {
+    let Some(entry) = entries.get(index) else {
+        return String::new();
+    };
+    format!(
+        "{}. {} - {} - {}",
+        index + 1,
+        entry.name,
+        entry.score,
+        format_time(Duration::from_secs_f32(entry.time.max(0.)))
+    )
+}
+
+/// Rows past the end of the table take up no space.
+fn row_display(entries: &[HighScore], index: usize) -> Display {
+    if index < entries.len() {
+        Display::Flex
+    } else {
+        Display::None
+    }
+}
@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{post_process::BloomPreset, save::storage};
+
+const SETTINGS_KEY: &str = "settings";
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_settings())
+            .add_systems(Update, save_settings.run_if(resource_changed::<Settings>()));
+    }
+}
+
+/// Player facing options, persisted between sessions.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub outlines: bool,
+    /// Disables screen shake, hit-stop and flashes
+    pub reduced_motion: bool,
+    /// Scales screen shake, hit-stop and flashes, from 0 to 1
+    pub effects_intensity: f32,
+    pub shadow_quality: ShadowQuality,
+    /// Quantises the 3D view to the game's colour palette
+    pub palette: bool,
+    pub vignette: bool,
+    pub chromatic_aberration: bool,
+    pub film_grain: bool,
+    pub bloom: BloomPreset,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            outlines: true,
+            reduced_motion: false,
+            effects_intensity: 1.,
+            shadow_quality: if cfg!(target_arch = "wasm32") {
+                ShadowQuality::Low
+            } else {
+                ShadowQuality::High
+            },
+            palette: false,
+            vignette: true,
+            chromatic_aberration: false,
+            film_grain: false,
+            bloom: BloomPreset::Off,
+        }
+    }
+}
+
+impl Settings {
+    /// How strongly screen effects should be applied, from 0 (disabled) to 1.
+    pub fn effect_scale(&self) -> f32 {
+        if self.reduced_motion {
+            0.
+        } else {
+            self.effects_intensity.clamp(0., 1.)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShadowQuality {
+    Off,
+    /// A single, smaller cascade
+    Low,
+    High,
+}
+
+impl ShadowQuality {
+    pub fn enabled(&self) -> bool {
+        !matches!(self, Self::Off)
+    }
+
+    /// The size of the directional light shadow maps.
+    pub fn map_size(&self) -> usize {
+        match self {
+            Self::Low => 1024,
+            _ => 2048,
+        }
+    }
+
+    /// The most shadow cascades a directional light can use.
+    pub fn max_cascades(&self) -> usize {
+        match self {
+            Self::High => 4,
+            _ => 1,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Off => Self::Low,
+            Self::Low => Self::High,
+            Self::High => Self::Off,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Low => "Low",
+            Self::High => "High",
+        }
+    }
+}
+
+fn load_settings() -> Settings {
+    match storage::read(SETTINGS_KEY) {
+        Ok(Some(contents)) => ron::from_str(&contents).unwrap_or_else(|e| {
+            warn!("Couldn't parse settings, using defaults: {e}");
+            Settings::default()
+        }),
+        Ok(None) => Settings::default(),
+        Err(e) => {
+            warn!("Couldn't read settings, using defaults: {e}");
+            Settings::default()
+        }
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    let result = ron::ser::to_string_pretty(settings.as_ref(), Default::default())
+        .map_err(|e| e.to_string())
+        .and_then(|contents| storage::write(SETTINGS_KEY, &contents).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        error!("Couldn't save settings: {e}");
+    }
+}
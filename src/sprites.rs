@@ -0,0 +1,61 @@
+use bevy::{prelude::*, transform::TransformSystem};
+
+/// The depth each [`YSort`] layer occupies - the 2D camera sees depths between 0 and 1000.
+const LAYER_DEPTH: f32 = 100.;
+/// The highest layer that fits in the 2D camera's depth range.
+pub const MAX_LAYER: u8 = 9;
+/// How much depth changes per world unit along the Y axis.
+const Y_SORT_SCALE: f32 = 0.001;
+
+pub struct SpritesPlugin;
+
+impl Plugin for SpritesPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<YSort>().add_systems(
+            PostUpdate,
+            y_sort.before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Sets the depth of an entity from its Y position, so things lower on screen are drawn in front.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct YSort {
+    /// Entities on higher layers are always drawn in front of those on lower ones.
+    /// Layers above [`MAX_LAYER`] are drawn on it.
+    pub layer: u8,
+    /// Moves the point used for sorting - e.g. down to a character's feet
+    pub offset: f32,
+}
+
+impl YSort {
+    #[allow(dead_code)]
+    pub fn new(layer: u8) -> Self {
+        Self { layer, offset: 0. }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    fn depth(&self, y: f32) -> f32 {
+        let min = self.layer.min(MAX_LAYER) as f32 * LAYER_DEPTH;
+        let depth = min + LAYER_DEPTH / 2. - (y + self.offset) * Y_SORT_SCALE;
+        depth.clamp(min, min + LAYER_DEPTH - Y_SORT_SCALE)
+    }
+}
+
+type MovedSpriteQuery<'w, 's, 'a> =
+    Query<'w, 's, (&'a YSort, &'a mut Transform), Or<(Changed<Transform>, Changed<YSort>)>>;
+
+fn y_sort(mut sorted: MovedSpriteQuery) {
+    for (sort, mut transform) in sorted.iter_mut() {
+        let depth = sort.depth(transform.translation.y);
+        if transform.translation.z != depth {
+            transform.translation.z = depth;
+        }
+    }
+}
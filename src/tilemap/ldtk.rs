@@ -0,0 +1,256 @@
+//! Reads the parts of an [LDtk](https://ldtk.io) project needed to spawn its levels.
+//! Projects need to be saved as a single file, without "Save levels to separate files".
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, BoxedFuture, LoadContext},
+    prelude::*,
+    utils::HashMap,
+};
+use serde::Deserialize;
+
+use super::{Tile, TileCollider, TileLayer, TilemapEntity, TilemapLevel, TilemapProject, Tileset};
+
+#[derive(Deserialize)]
+struct Project {
+    defs: Definitions,
+    levels: Vec<Level>,
+}
+
+#[derive(Deserialize)]
+struct Definitions {
+    tilesets: Vec<TilesetDefinition>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TilesetDefinition {
+    uid: i32,
+    /// Missing for the embedded icon tileset
+    rel_path: Option<String>,
+    tile_grid_size: f32,
+    #[serde(default)]
+    spacing: f32,
+    #[serde(default)]
+    padding: f32,
+    #[serde(rename = "__cWid")]
+    columns: usize,
+    #[serde(rename = "__cHei")]
+    rows: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Level {
+    identifier: String,
+    px_wid: f32,
+    px_hei: f32,
+    #[serde(default)]
+    layer_instances: Vec<Layer>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Layer {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__cWid")]
+    columns: usize,
+    #[serde(rename = "__gridSize")]
+    grid_size: f32,
+    #[serde(rename = "__tilesetDefUid")]
+    tileset: Option<i32>,
+    #[serde(rename = "__pxTotalOffsetX", default)]
+    offset_x: f32,
+    #[serde(rename = "__pxTotalOffsetY", default)]
+    offset_y: f32,
+    #[serde(default)]
+    int_grid_csv: Vec<i32>,
+    #[serde(default)]
+    grid_tiles: Vec<LayerTile>,
+    #[serde(default)]
+    auto_layer_tiles: Vec<LayerTile>,
+    #[serde(default)]
+    entity_instances: Vec<EntityInstance>,
+}
+
+#[derive(Deserialize)]
+struct LayerTile {
+    px: [f32; 2],
+    /// Bit 0 flips the tile horizontally, bit 1 vertically
+    f: u8,
+    t: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EntityInstance {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__pivot")]
+    pivot: [f32; 2],
+    px: [f32; 2],
+    width: f32,
+    height: f32,
+    #[serde(default)]
+    field_instances: Vec<Field>,
+}
+
+#[derive(Deserialize)]
+struct Field {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__value")]
+    value: serde_json::Value,
+}
+
+#[derive(Default)]
+pub(super) struct LdtkLoader;
+
+impl AssetLoader for LdtkLoader {
+    type Asset = TilemapProject;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let project: Project = serde_json::from_slice(&bytes)?;
+
+            let directory = load_context
+                .path()
+                .parent()
+                .map(|path| path.to_path_buf())
+                .unwrap_or_default();
+            let tilesets = project
+                .defs
+                .tilesets
+                .iter()
+                .map(|tileset| (tileset.uid, tileset))
+                .collect::<HashMap<_, _>>();
+
+            let mut levels = HashMap::default();
+            for level in project.levels {
+                let identifier = level.identifier.clone();
+                let handle = load_context.labeled_asset_scope(identifier.clone(), |context| {
+                    // LDtk lists layers from the top down, and measures y downwards from the top
+                    let mut layers = vec![];
+                    let mut colliders = vec![];
+                    let mut entities = vec![];
+                    for layer in level.layer_instances.iter().rev() {
+                        let to_world = |x: f32, y: f32| {
+                            Vec2::new(x + layer.offset_x, level.px_hei - (y + layer.offset_y))
+                        };
+
+                        let tileset =
+                            layer
+                                .tileset
+                                .and_then(|uid| tilesets.get(&uid))
+                                .and_then(|tileset| {
+                                    let path = directory.join(tileset.rel_path.as_ref()?);
+                                    Some(Tileset {
+                                        image: context.load(path),
+                                        tile_size: tileset.tile_grid_size,
+                                        columns: tileset.columns,
+                                        rows: tileset.rows,
+                                        spacing: tileset.spacing,
+                                        padding: tileset.padding,
+                                    })
+                                });
+                        if let Some(tileset) = tileset {
+                            let half = layer.grid_size / 2.;
+                            let tiles = layer
+                                .grid_tiles
+                                .iter()
+                                .chain(layer.auto_layer_tiles.iter())
+                                .map(|tile| Tile {
+                                    position: to_world(tile.px[0] + half, tile.px[1] + half),
+                                    index: tile.t,
+                                    flip_x: tile.f & 1 != 0,
+                                    flip_y: tile.f & 2 != 0,
+                                })
+                                .collect::<Vec<_>>();
+                            if !tiles.is_empty() {
+                                layers.push(TileLayer {
+                                    name: layer.identifier.clone(),
+                                    tileset,
+                                    size: layer.grid_size,
+                                    tiles,
+                                });
+                            }
+                        }
+
+                        // Neighbouring cells with the same value in a row share a collider
+                        if layer.columns > 0 {
+                            for (row, cells) in layer.int_grid_csv.chunks(layer.columns).enumerate()
+                            {
+                                let mut column = 0;
+                                while column < cells.len() {
+                                    let value = cells[column];
+                                    let start = column;
+                                    while column < cells.len() && cells[column] == value {
+                                        column += 1;
+                                    }
+                                    if value == 0 {
+                                        continue;
+                                    }
+                                    let size = Vec2::new(
+                                        (column - start) as f32 * layer.grid_size,
+                                        layer.grid_size,
+                                    );
+                                    let top_left = Vec2::new(
+                                        start as f32 * layer.grid_size,
+                                        row as f32 * layer.grid_size,
+                                    );
+                                    colliders.push(TileCollider {
+                                        position: to_world(
+                                            top_left.x + size.x / 2.,
+                                            top_left.y + size.y / 2.,
+                                        ),
+                                        size,
+                                        value,
+                                    });
+                                }
+                            }
+                        }
+
+                        entities.extend(layer.entity_instances.iter().map(|entity| {
+                            let size = Vec2::new(entity.width, entity.height);
+                            let center = Vec2::from(entity.px)
+                                + (Vec2::splat(0.5) - Vec2::from(entity.pivot)) * size;
+                            TilemapEntity {
+                                identifier: entity.identifier.clone(),
+                                position: to_world(center.x, center.y),
+                                size,
+                                fields: entity
+                                    .field_instances
+                                    .iter()
+                                    .map(|field| (field.identifier.clone(), field.value.clone()))
+                                    .collect(),
+                            }
+                        }));
+                    }
+
+                    TilemapLevel {
+                        name: identifier.clone(),
+                        size: Vec2::new(level.px_wid, level.px_hei),
+                        layers,
+                        colliders,
+                        entities,
+                    }
+                });
+                levels.insert(identifier, handle);
+            }
+
+            Ok(TilemapProject { levels })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ldtk"]
+    }
+}
@@ -0,0 +1,209 @@
+//! Tilemaps for 2D levels, authored in [LDtk](https://ldtk.io).
+//!
+//! Each level in a `.ldtk` project is a labeled [`TilemapLevel`] asset, like `maps/world.ldtk#Level_0`.
+//! A [`Tilemap`] spawns its tiles as sprites, its IntGrid cells as [`TileCollider`]s, and its entities
+//! as prefabs named after their identifier in snake case. Saving the project in LDtk rebuilds it.
+use bevy::{prelude::*, reflect::TypePath, utils::HashMap};
+
+use crate::prefabs::SpawnPrefab;
+
+mod ldtk;
+
+/// How far apart each tile layer is drawn, keeping them all below the first [`crate::sprites::YSort`] layer.
+const TILE_LAYER_DEPTH: f32 = 0.1;
+
+pub struct TilemapPlugin;
+
+impl Plugin for TilemapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<TilemapProject>()
+            .init_asset::<TilemapLevel>()
+            .init_asset_loader::<ldtk::LdtkLoader>()
+            .add_systems(Update, spawn_tilemaps);
+    }
+}
+
+/// A whole LDtk project, with each of its levels by identifier.
+#[derive(Asset, TypePath, Debug)]
+pub struct TilemapProject {
+    #[allow(dead_code)]
+    pub levels: HashMap<String, Handle<TilemapLevel>>,
+}
+
+#[derive(Asset, TypePath, Debug)]
+pub struct TilemapLevel {
+    #[allow(dead_code)]
+    pub name: String,
+    /// The size of the level in pixels, with its bottom left corner at the origin
+    #[allow(dead_code)]
+    pub size: Vec2,
+    /// From the bottom up
+    pub layers: Vec<TileLayer>,
+    pub colliders: Vec<TileCollider>,
+    pub entities: Vec<TilemapEntity>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TileLayer {
+    pub name: String,
+    pub tileset: Tileset,
+    pub size: f32,
+    pub tiles: Vec<Tile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tileset {
+    pub image: Handle<Image>,
+    pub tile_size: f32,
+    pub columns: usize,
+    pub rows: usize,
+    pub spacing: f32,
+    pub padding: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Tile {
+    /// The centre of the tile
+    pub position: Vec2,
+    pub index: usize,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+/// A solid area of an IntGrid layer.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TileCollider {
+    pub position: Vec2,
+    pub size: Vec2,
+    /// The IntGrid value of the cells, for telling different kinds of ground apart
+    #[allow(dead_code)]
+    pub value: i32,
+}
+
+/// An entity placed in the level, spawned with the prefab matching its identifier.
+#[derive(Component, Debug, Clone)]
+pub struct TilemapEntity {
+    pub identifier: String,
+    /// The centre of the entity
+    pub position: Vec2,
+    #[allow(dead_code)]
+    pub size: Vec2,
+    #[allow(dead_code)]
+    pub fields: HashMap<String, serde_json::Value>,
+}
+
+/// Spawns a tilemap level as children of this entity.
+#[derive(Component, Debug, Clone)]
+pub struct Tilemap(pub Handle<TilemapLevel>);
+
+/// Something spawned by a [`Tilemap`], replaced when it's rebuilt.
+#[derive(Component)]
+struct TilemapPart;
+
+fn spawn_tilemaps(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<TilemapLevel>>,
+    levels: Res<Assets<TilemapLevel>>,
+    mut atlases: ResMut<Assets<TextureAtlas>>,
+    tilemaps: Query<(Entity, Ref<Tilemap>, Option<&Children>)>,
+    parts: Query<(), With<TilemapPart>>,
+) {
+    let changed = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, tilemap, children) in tilemaps.iter() {
+        if !tilemap.is_added() && !changed.contains(&tilemap.0.id()) {
+            continue;
+        }
+        let Some(level) = levels.get(&tilemap.0) else {
+            continue;
+        };
+
+        for child in children.into_iter().flatten() {
+            if parts.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+
+        commands.entity(entity).with_children(|p| {
+            for (depth, layer) in level.layers.iter().enumerate() {
+                let tileset = &layer.tileset;
+                let atlas = atlases.add(TextureAtlas::from_grid(
+                    tileset.image.clone(),
+                    Vec2::splat(tileset.tile_size),
+                    tileset.columns,
+                    tileset.rows,
+                    Some(Vec2::splat(tileset.spacing)),
+                    Some(Vec2::splat(tileset.padding)),
+                ));
+                p.spawn((
+                    Name::new(layer.name.clone()),
+                    TilemapPart,
+                    SpatialBundle::from_transform(Transform::from_xyz(
+                        0.,
+                        0.,
+                        depth as f32 * TILE_LAYER_DEPTH,
+                    )),
+                ))
+                .with_children(|p| {
+                    for tile in layer.tiles.iter() {
+                        p.spawn(SpriteSheetBundle {
+                            texture_atlas: atlas.clone(),
+                            sprite: TextureAtlasSprite {
+                                index: tile.index,
+                                flip_x: tile.flip_x,
+                                flip_y: tile.flip_y,
+                                custom_size: Some(Vec2::splat(layer.size)),
+                                ..default()
+                            },
+                            transform: Transform::from_translation(tile.position.extend(0.)),
+                            ..default()
+                        });
+                    }
+                });
+            }
+
+            for collider in level.colliders.iter() {
+                p.spawn((
+                    TilemapPart,
+                    *collider,
+                    TransformBundle::from_transform(Transform::from_translation(
+                        collider.position.extend(0.),
+                    )),
+                ));
+            }
+
+            for tilemap_entity in level.entities.iter() {
+                let id = p
+                    .spawn((
+                        Name::new(tilemap_entity.identifier.clone()),
+                        TilemapPart,
+                        tilemap_entity.clone(),
+                        SpatialBundle::from_transform(Transform::from_translation(
+                            tilemap_entity.position.extend(0.),
+                        )),
+                    ))
+                    .id();
+                let prefab = to_snake_case(&tilemap_entity.identifier);
+                p.add_command(SpawnPrefab::new(prefab).with_parent(id));
+            }
+        });
+    }
+}
+
+/// Turns an LDtk identifier like `PlayerStart` into a prefab name like `player_start`.
+fn to_snake_case(identifier: &str) -> String {
+    let mut name = String::with_capacity(identifier.len());
+    for (i, c) in identifier.chars().enumerate() {
+        if c.is_uppercase() && i > 0 && !name.ends_with('_') {
+            name.push('_');
+        }
+        name.extend(c.to_lowercase());
+    }
+    name
+}
@@ -0,0 +1,98 @@
+use bevy::{
+    asset::*,
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+    render::{render_asset::RenderAssets, render_resource::*},
+};
+
+mod outline;
+mod ramp;
+mod toonify;
+
+use self::{outline::OutlinePlugin, ramp::RampPlugin, toonify::ToonifyPlugin};
+
+pub struct ToonMaterialPlugin;
+
+impl Plugin for ToonMaterialPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MaterialPlugin::<ToonMaterial>::default(),
+            ToonifyPlugin,
+            OutlinePlugin,
+            RampPlugin,
+        ));
+    }
+}
+
+const TOON_MATERIAL_FLAGS_COLOR_TEXTURE: u32 = 1;
+const TOON_MATERIAL_FLAGS_SHADOW_TEXTURE: u32 = 2;
+
+#[derive(AsBindGroup, TypeUuid, TypePath, Debug, Clone, Asset)]
+#[uuid = "dfaf271e-ec36-4fdd-a17d-0c0c79964926"]
+#[uniform(0, ToonMaterialUniform)]
+pub struct ToonMaterial {
+    /// Tints the colour texture, or is used as the colour if there is no texture
+    pub base_color: Color,
+    /// The number of distinct light bands - 0 disables banding
+    pub band_count: u32,
+    /// How strongly the silhouette facing away from the camera gets lit
+    pub rim_strength: f32,
+    /// The specular intensity above which a highlight gets drawn - 1 or above disables highlights
+    pub specular_step: f32,
+
+    #[texture(1)]
+    #[sampler(2)]
+    pub color_texture: Option<Handle<Image>>,
+
+    /// A ramp sampled horizontally by the light level - if missing, the light level is used directly
+    #[texture(3)]
+    #[sampler(4)]
+    pub shadow_texture: Option<Handle<Image>>,
+}
+
+impl Default for ToonMaterial {
+    fn default() -> Self {
+        Self {
+            base_color: Color::WHITE,
+            band_count: 3,
+            rim_strength: 0.3,
+            specular_step: 0.9,
+            color_texture: None,
+            shadow_texture: None,
+        }
+    }
+}
+
+impl Material for ToonMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/toon_shader.wgsl".into()
+    }
+}
+
+#[derive(Clone, Default, ShaderType)]
+pub struct ToonMaterialUniform {
+    pub base_color: Vec4,
+    pub band_count: f32,
+    pub rim_strength: f32,
+    pub specular_step: f32,
+    pub flags: u32,
+}
+
+impl AsBindGroupShaderType<ToonMaterialUniform> for ToonMaterial {
+    fn as_bind_group_shader_type(&self, _images: &RenderAssets<Image>) -> ToonMaterialUniform {
+        let mut flags = 0;
+        if self.color_texture.is_some() {
+            flags |= TOON_MATERIAL_FLAGS_COLOR_TEXTURE;
+        }
+        if self.shadow_texture.is_some() {
+            flags |= TOON_MATERIAL_FLAGS_SHADOW_TEXTURE;
+        }
+        ToonMaterialUniform {
+            base_color: self.base_color.as_linear_rgba_f32().into(),
+            band_count: self.band_count as f32,
+            rim_strength: self.rim_strength,
+            specular_step: self.specular_step,
+            flags,
+        }
+    }
+}
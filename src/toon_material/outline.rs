@@ -0,0 +1,206 @@
+use bevy::{
+    pbr::{MaterialPipeline, MaterialPipelineKey, NotShadowCaster},
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+    render::{
+        mesh::MeshVertexBufferLayout,
+        render_resource::{
+            AsBindGroup, Face, RenderPipelineDescriptor, ShaderRef, SpecializedMeshPipelineError,
+        },
+    },
+    utils::{HashMap, HashSet},
+};
+
+use crate::settings::Settings;
+
+pub struct OutlinePlugin;
+
+impl Plugin for OutlinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<OutlineMaterial>::default())
+            .init_resource::<OutlineMaterials>()
+            .add_systems(
+                PostUpdate,
+                (
+                    spawn_outline_hulls,
+                    update_outline_materials,
+                    toggle_outlines.run_if(resource_changed::<Settings>()),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Draws an outline around the meshes of this entity and all its descendants,
+/// using an inverted hull - a copy of the mesh pushed out along its normals with its front faces culled.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Outline {
+    pub color: Color,
+    /// The width of the outline, in world units
+    pub width: f32,
+}
+
+impl Default for Outline {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            width: 0.02,
+        }
+    }
+}
+
+#[derive(AsBindGroup, TypeUuid, TypePath, Debug, Clone, Asset)]
+#[uuid = "0f1a3c53-7a3e-4c8e-9a4b-6f1d2f3e8b21"]
+pub struct OutlineMaterial {
+    #[uniform(0)]
+    pub color: Color,
+    #[uniform(0)]
+    pub width: f32,
+}
+
+impl Material for OutlineMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/outline.wgsl".into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "shaders/outline.wgsl".into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        descriptor.primitive.cull_mode = Some(Face::Front);
+        Ok(())
+    }
+}
+
+/// The hull drawing the outline for the mesh it is a child of.
+#[derive(Component)]
+struct OutlineHull {
+    source: Entity,
+}
+
+#[derive(Component)]
+struct HasOutlineHull;
+
+/// Caches materials, so outlines with the same colour & width share one.
+#[derive(Resource, Default)]
+struct OutlineMaterials(HashMap<([u32; 4], u32), Handle<OutlineMaterial>>);
+
+impl OutlineMaterials {
+    fn get(
+        &mut self,
+        outline: &Outline,
+        materials: &mut Assets<OutlineMaterial>,
+    ) -> Handle<OutlineMaterial> {
+        let key = (
+            outline.color.as_rgba_f32().map(f32::to_bits),
+            outline.width.to_bits(),
+        );
+        self.0
+            .entry(key)
+            .or_insert_with(|| {
+                materials.add(OutlineMaterial {
+                    color: outline.color,
+                    width: outline.width,
+                })
+            })
+            .clone()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_outline_hulls(
+    mut commands: Commands,
+    added_outlines: Query<Entity, Added<Outline>>,
+    added_meshes: Query<Entity, (Added<Handle<Mesh>>, Without<OutlineHull>)>,
+    meshes: Query<&Handle<Mesh>, (Without<OutlineHull>, Without<HasOutlineHull>)>,
+    outlines: Query<&Outline>,
+    parents: Query<&Parent>,
+    children: Query<&Children>,
+    mut materials: ResMut<Assets<OutlineMaterial>>,
+    mut cache: ResMut<OutlineMaterials>,
+    settings: Res<Settings>,
+) {
+    let from_outlines = added_outlines
+        .iter()
+        .flat_map(|e| std::iter::once(e).chain(children.iter_descendants(e)));
+    let candidates = from_outlines.chain(added_meshes.iter());
+    let mut seen = HashSet::default();
+
+    for entity in candidates {
+        if !seen.insert(entity) {
+            continue;
+        }
+        let Ok(mesh) = meshes.get(entity) else {
+            continue;
+        };
+        let Some((source, outline)) = std::iter::once(entity)
+            .chain(parents.iter_ancestors(entity))
+            .find_map(|e| outlines.get(e).ok().map(|o| (e, o)))
+        else {
+            continue;
+        };
+
+        let material = cache.get(outline, &mut materials);
+        let visibility = if settings.outlines {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        commands
+            .entity(entity)
+            .insert(HasOutlineHull)
+            .with_children(|p| {
+                p.spawn((
+                    MaterialMeshBundle {
+                        mesh: mesh.clone(),
+                        material,
+                        visibility,
+                        ..default()
+                    },
+                    OutlineHull { source },
+                    NotShadowCaster,
+                ));
+            });
+    }
+}
+
+fn update_outline_materials(
+    changed: Query<Entity, Changed<Outline>>,
+    outlines: Query<&Outline>,
+    mut hulls: Query<(&OutlineHull, &mut Handle<OutlineMaterial>)>,
+    mut materials: ResMut<Assets<OutlineMaterial>>,
+    mut cache: ResMut<OutlineMaterials>,
+) {
+    if changed.is_empty() {
+        return;
+    }
+    for (hull, mut material) in hulls.iter_mut() {
+        if !changed.contains(hull.source) {
+            continue;
+        }
+        let Ok(outline) = outlines.get(hull.source) else {
+            continue;
+        };
+        *material = cache.get(outline, &mut materials);
+    }
+}
+
+fn toggle_outlines(settings: Res<Settings>, mut hulls: Query<&mut Visibility, With<OutlineHull>>) {
+    let visibility = if settings.outlines {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut hull in hulls.iter_mut() {
+        if *hull != visibility {
+            *hull = visibility;
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Shadow ramps for the [`ToonMaterial`], authored as a list of colour stops in a `.ramp.yaml`
+//! file and baked into a 1D [`Image`] when loaded.
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, BoxedFuture, LoadContext},
+    prelude::*,
+    render::{
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+};
+use serde::Deserialize;
+
+use super::ToonMaterial;
+
+pub struct RampPlugin;
+
+impl Plugin for RampPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset_loader::<RampLoader>()
+            .add_systems(Update, refresh_materials_on_ramp_change);
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RampMode {
+    /// Each stop's colour is used until the next stop
+    #[default]
+    Hard,
+    /// Colours are blended between stops
+    Soft,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct RampStop {
+    pub position: f32,
+    /// An sRGB colour
+    pub color: [f32; 3],
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Ramp {
+    #[serde(default)]
+    pub mode: RampMode,
+    #[serde(default = "default_resolution")]
+    pub resolution: u32,
+    pub stops: Vec<RampStop>,
+}
+
+fn default_resolution() -> u32 {
+    256
+}
+
+impl Ramp {
+    pub fn sample(&self, position: f32) -> [f32; 3] {
+        let Some(first) = self.stops.first() else {
+            return [1., 1., 1.];
+        };
+        let mut previous = first;
+        for stop in self.stops.iter() {
+            if stop.position > position {
+                return match self.mode {
+                    RampMode::Hard => previous.color,
+                    RampMode::Soft if stop.position <= previous.position => stop.color,
+                    RampMode::Soft => {
+                        let t = ((position - previous.position)
+                            / (stop.position - previous.position))
+                            .clamp(0., 1.);
+                        [0, 1, 2]
+                            .map(|i| previous.color[i] + (stop.color[i] - previous.color[i]) * t)
+                    }
+                };
+            }
+            previous = stop;
+        }
+        previous.color
+    }
+
+    pub fn bake(&self) -> Image {
+        let width = self.resolution.max(1);
+        let data = (0..width)
+            .flat_map(|x| {
+                let position = (x as f32 + 0.5) / width as f32;
+                let [r, g, b] = self
+                    .sample(position)
+                    .map(|c| (c.clamp(0., 1.) * 255.).round() as u8);
+                [r, g, b, 255]
+            })
+            .collect();
+
+        let mut image = Image::new(
+            Extent3d {
+                width,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        image.sampler = match self.mode {
+            RampMode::Hard => ImageSampler::nearest(),
+            RampMode::Soft => ImageSampler::linear(),
+        };
+        image
+    }
+}
+
+#[derive(Default)]
+struct RampLoader;
+
+impl AssetLoader for RampLoader {
+    type Asset = Image;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let mut ramp: Ramp = serde_yaml::from_slice(&bytes)?;
+            ramp.stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+            Ok(ramp.bake())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ramp.yaml"]
+    }
+}
+
+/// Prepared materials keep using the old texture when an image is reloaded,
+/// so materials using a modified ramp get flagged as changed too.
+fn refresh_materials_on_ramp_change(
+    mut events: EventReader<AssetEvent<Image>>,
+    mut materials: ResMut<Assets<ToonMaterial>>,
+) {
+    let modified = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if modified.is_empty() {
+        return;
+    }
+    let affected = materials
+        .iter()
+        .filter(|(_, material)| {
+            material
+                .shadow_texture
+                .as_ref()
+                .is_some_and(|ramp| modified.contains(&ramp.id()))
+        })
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    for id in affected {
+        materials.get_mut(id);
+    }
+}
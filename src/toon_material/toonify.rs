@@ -0,0 +1,86 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::ToonMaterial;
+
+pub struct ToonifyPlugin;
+
+impl Plugin for ToonifyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ToonifiedMaterials>()
+            .add_systems(PostUpdate, toonify_materials);
+    }
+}
+
+/// Replaces the [`StandardMaterial`]s on this entity and all its descendants,
+/// such as the meshes of a spawned glTF scene, with equivalent [`ToonMaterial`]s.
+#[derive(Component, Clone, Debug, Default)]
+pub struct Toonify {
+    pub ramp: Option<Handle<Image>>,
+}
+
+impl Toonify {
+    #[allow(dead_code)]
+    pub fn with_ramp(ramp: Handle<Image>) -> Self {
+        Self { ramp: Some(ramp) }
+    }
+}
+
+/// Marks a mesh whose [`StandardMaterial`] hadn't loaded yet, so it's converted once it has.
+#[derive(Component)]
+struct PendingToonify;
+
+/// Caches converted materials, so meshes sharing a [`StandardMaterial`] also share a [`ToonMaterial`].
+#[derive(Resource, Default)]
+struct ToonifiedMaterials(HashMap<ToonifiedKey, Handle<ToonMaterial>>);
+
+/// A standard material and the ramp it was converted with.
+type ToonifiedKey = (AssetId<StandardMaterial>, Option<AssetId<Image>>);
+
+type UnconvertedMeshQuery<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (Entity, &'a Handle<StandardMaterial>),
+    Or<(Added<Handle<StandardMaterial>>, With<PendingToonify>)>,
+>;
+
+fn toonify_materials(
+    mut commands: Commands,
+    meshes: UnconvertedMeshQuery,
+    toonify: Query<&Toonify>,
+    parents: Query<&Parent>,
+    standard_materials: Res<Assets<StandardMaterial>>,
+    mut toon_materials: ResMut<Assets<ToonMaterial>>,
+    mut cache: ResMut<ToonifiedMaterials>,
+) {
+    for (entity, standard) in meshes.iter() {
+        let Some(settings) = std::iter::once(entity)
+            .chain(parents.iter_ancestors(entity))
+            .find_map(|e| toonify.get(e).ok())
+        else {
+            continue;
+        };
+        let Some(material) = standard_materials.get(standard) else {
+            commands.entity(entity).insert(PendingToonify);
+            continue;
+        };
+
+        let key = (standard.id(), settings.ramp.as_ref().map(|ramp| ramp.id()));
+        let toon = cache
+            .0
+            .entry(key)
+            .or_insert_with(|| {
+                toon_materials.add(ToonMaterial {
+                    base_color: material.base_color,
+                    color_texture: material.base_color_texture.clone(),
+                    shadow_texture: settings.ramp.clone(),
+                    ..default()
+                })
+            })
+            .clone();
+
+        commands
+            .entity(entity)
+            .remove::<(Handle<StandardMaterial>, PendingToonify)>()
+            .insert(toon);
+    }
+}
@@ -21,6 +21,10 @@ pub fn overlay(b: &mut NodeBundle) {
     b.background_color.0 = OVERLAY_COLOR;
 }
 
+pub fn modal(b: &mut NodeBundle) {
+    b.z_index = ZIndex::Global(25);
+}
+
 pub fn opaque(b: &mut dyn IntermediaryNodeBundleHandler) {
     b.background_color().0 = colors::SCREEN_BACKGROUND_COLOR;
 }
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use super::super::colors::*;
+
+pub fn dialogue_root(b: &mut NodeBundle) {
+    b.style.width = Val::Percent(100.);
+    b.style.display = Display::Flex;
+    b.style.justify_content = JustifyContent::Center;
+    b.style.position_type = PositionType::Absolute;
+    b.style.left = Val::Px(0.);
+    b.style.bottom = Val::Px(20.);
+    b.z_index = ZIndex::Global(15);
+}
+
+pub fn dialogue_box(b: &mut NodeBundle) {
+    b.style.width = Val::Percent(80.);
+    b.style.max_width = Val::Px(900.);
+    b.style.min_height = Val::Px(150.);
+    b.style.padding = UiRect::all(Val::Px(20.));
+    b.style.border = UiRect::all(Val::Px(2.));
+    b.style.display = Display::Flex;
+    b.style.flex_direction = FlexDirection::Column;
+    b.style.row_gap = Val::Px(10.);
+    b.background_color.0 = PRIMARY_BACKGROUND_COLOR;
+    b.border_color.0 = BORDER_COLOR;
+}
+
+pub fn dialogue_choices(b: &mut NodeBundle) {
+    b.style.display = Display::Flex;
+    b.style.flex_direction = FlexDirection::Column;
+    b.style.align_items = AlignItems::FlexEnd;
+}
+
+pub fn dialogue_speaker_text(assets: &AssetServer, t: &mut TextStyle) {
+    t.font_size = 24.;
+    t.color = PRIMARY_COLOR_PRIORITIZED;
+    t.font = assets.load("fonts/AMERSN__.ttf");
+}
+
+pub fn dialogue_text(assets: &AssetServer, t: &mut TextStyle) {
+    t.font_size = 20.;
+    t.color = PRIMARY_COLOR;
+    t.font = assets.load("fonts/AMERSN__.ttf");
+}
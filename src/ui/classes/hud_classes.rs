@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::ui::intermediary_node_bundles::IntermediaryNodeBundleHandler;
+
+use super::super::colors::*;
+
+pub fn hud_root(b: &mut NodeBundle) {
+    b.style.width = Val::Percent(100.);
+    b.style.height = Val::Percent(100.);
+    b.style.display = Display::Flex;
+    b.style.flex_direction = FlexDirection::Column;
+    b.style.justify_content = JustifyContent::SpaceBetween;
+    b.style.position_type = PositionType::Absolute;
+    b.style.left = Val::Px(0.);
+    b.style.top = Val::Px(0.);
+    b.style.padding = UiRect::all(Val::Px(10.));
+    b.z_index = ZIndex::Global(10);
+}
+
+pub fn hud_row(b: &mut NodeBundle) {
+    b.style.display = Display::Flex;
+    b.style.flex_direction = FlexDirection::Row;
+    b.style.justify_content = JustifyContent::SpaceBetween;
+    b.style.align_items = AlignItems::FlexStart;
+}
+
+pub fn hud_group(b: &mut NodeBundle) {
+    b.style.display = Display::Flex;
+    b.style.flex_direction = FlexDirection::Column;
+    b.style.row_gap = Val::Px(5.);
+}
+
+pub fn hud_bar(b: &mut dyn IntermediaryNodeBundleHandler) {
+    b.style().width = Val::Px(200.);
+    b.style().height = Val::Px(20.);
+    b.style().border = UiRect::all(Val::Px(2.));
+    b.background_color().0 = HUD_BAR_BACKGROUND_COLOR;
+    if let Some(border) = b.border_color() {
+        border.0 = BORDER_COLOR;
+    }
+}
+
+pub fn hud_bar_fill(b: &mut dyn IntermediaryNodeBundleHandler) {
+    b.style().width = Val::Percent(100.);
+    b.style().height = Val::Percent(100.);
+    b.background_color().0 = HUD_BAR_FILL_COLOR;
+}
+
+pub fn hud_icon_row(b: &mut dyn IntermediaryNodeBundleHandler) {
+    b.style().display = Display::Flex;
+    b.style().flex_direction = FlexDirection::Row;
+    b.style().column_gap = Val::Px(4.);
+    b.style().align_items = AlignItems::Center;
+}
+
+pub fn hud_text(assets: &AssetServer, t: &mut TextStyle) {
+    t.font_size = 30.;
+    t.color = PRIMARY_COLOR;
+    t.font = assets.load("fonts/AMERSN__.ttf");
+}
@@ -1,3 +1,11 @@
 mod base_classes;
+mod dialogue_classes;
+mod hud_classes;
+mod score_classes;
+mod toast_classes;
 
 pub use base_classes::*;
+pub use dialogue_classes::*;
+pub use hud_classes::*;
+pub use score_classes::*;
+pub use toast_classes::*;
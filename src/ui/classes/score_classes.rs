@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+use super::super::colors::*;
+
+pub fn high_score_list(b: &mut NodeBundle) {
+    b.style.display = Display::Flex;
+    b.style.flex_direction = FlexDirection::Column;
+    b.style.align_items = AlignItems::Center;
+    b.style.row_gap = Val::Px(4.);
+}
+
+pub fn high_score_text(assets: &AssetServer, t: &mut TextStyle) {
+    t.font_size = 16.;
+    t.color = PRIMARY_COLOR;
+    t.font = assets.load("fonts/AMERSN__.ttf");
+}
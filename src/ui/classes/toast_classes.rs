@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use super::super::colors::*;
+
+pub fn toast_root(b: &mut NodeBundle) {
+    b.style.display = Display::Flex;
+    b.style.flex_direction = FlexDirection::ColumnReverse;
+    b.style.align_items = AlignItems::FlexEnd;
+    b.style.row_gap = Val::Px(8.);
+    b.style.position_type = PositionType::Absolute;
+    b.style.right = Val::Px(10.);
+    b.style.bottom = Val::Px(10.);
+    b.z_index = ZIndex::Global(30);
+}
+
+pub fn toast(b: &mut NodeBundle) {
+    b.style.padding = UiRect::axes(Val::Px(15.), Val::Px(8.));
+    b.style.border = UiRect::left(Val::Px(6.));
+    b.style.max_width = Val::Px(400.);
+    b.background_color.0 = PRIMARY_BACKGROUND_COLOR;
+    b.border_color.0 = TOAST_INFO_COLOR;
+}
+
+pub fn toast_warning(b: &mut NodeBundle) {
+    b.border_color.0 = TOAST_WARNING_COLOR;
+}
+
+pub fn toast_error(b: &mut NodeBundle) {
+    b.border_color.0 = TOAST_ERROR_COLOR;
+}
+
+pub fn toast_text(assets: &AssetServer, t: &mut TextStyle) {
+    t.font_size = 18.;
+    t.color = PRIMARY_COLOR;
+    t.font = assets.load("fonts/AMERSN__.ttf");
+}
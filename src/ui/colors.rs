@@ -1,5 +1,5 @@
 #![allow(dead_code)]
-use bevy::prelude::{AmbientLight, Color};
+use bevy::prelude::Color;
 
 pub const OVERLAY_COLOR: Color = Color::rgba(0., 0., 0., 0.9);
 pub const BORDER_COLOR: Color = Color::rgb(0.19, 0.25, 0.35);
@@ -14,9 +14,11 @@ pub const PRIMARY_COLOR_FOCUSED: Color = PRIMARY_COLOR_PRIORITIZED;
 pub const PRIMARY_COLOR_ACTIVE: Color = PRIMARY_COLOR_PRIORITIZED;
 pub const PRIMARY_COLOR_BLOCKED: Color = Color::rgb(0.48, 0.64, 0.74);
 
-pub const DEFAULT_AMBIENT: AmbientLight = AmbientLight {
-    color: Color::rgb(1., 1., 1.),
-    brightness: 0.26,
-};
+pub const HUD_BAR_BACKGROUND_COLOR: Color = Color::rgba(0., 0., 0., 0.5);
+pub const HUD_BAR_FILL_COLOR: Color = Color::rgb(0.85, 0.2, 0.25);
+
+pub const TOAST_INFO_COLOR: Color = PRIMARY_COLOR;
+pub const TOAST_WARNING_COLOR: Color = Color::rgb(0.96, 0.56, 0.2);
+pub const TOAST_ERROR_COLOR: Color = Color::rgb(0.85, 0.2, 0.25);
 
 pub const DEFAULT_CLEAR: Color = Color::rgb(0.75, 0.75, 0.75);
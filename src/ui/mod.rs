@@ -4,19 +4,20 @@ use bevy_ui_navigation::{systems::InputMapping, DefaultNavigationPlugins};
 
 use crate::assets::MainGameAssets;
 
-use self::buttons::apply_button_styles;
+use self::{buttons::apply_button_styles, toasts::ToastPlugin};
 
 pub mod buttons;
 pub mod classes;
 pub mod colors;
 
 pub mod intermediary_node_bundles;
+pub mod toasts;
 
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_plugins(DefaultNavigationPlugins)
+        app.add_plugins((DefaultNavigationPlugins, ToastPlugin))
             .insert_resource(InputMapping {
                 keyboard_navigation: true,
                 focus_follows_mouse: true,
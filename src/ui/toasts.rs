@@ -0,0 +1,171 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+use bevy_ui_dsl::*;
+use dexterous_developer::{
+    dexterous_developer_setup, ReloadableApp, ReloadableAppContents, ReloadableElementsSetup,
+};
+
+use super::classes::*;
+
+/// The maximum number of toasts displayed at once - any others wait in the queue.
+const MAX_TOASTS: usize = 4;
+const FADE_OUT_SECONDS: f32 = 0.5;
+const DEFAULT_DURATION_SECONDS: f32 = 3.;
+
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Notification>()
+            .init_resource::<ToastQueue>()
+            .add_systems(Startup, setup)
+            .setup_reloadable_elements::<reloadable>();
+    }
+}
+
+#[dexterous_developer_setup(toasts)]
+fn reloadable(app: &mut ReloadableAppContents) {
+    app.add_systems(
+        Update,
+        (queue_notifications, show_toasts, update_toasts).chain(),
+    );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+/// A non-blocking message for the player, displayed as a toast in the corner of the screen.
+#[derive(Event, Clone, Debug)]
+pub struct Notification {
+    pub message: String,
+    pub severity: Severity,
+    pub duration: Duration,
+}
+
+impl Notification {
+    pub fn new(message: impl Into<String>, severity: Severity) -> Self {
+        Self {
+            message: message.into(),
+            severity,
+            duration: Duration::from_secs_f32(DEFAULT_DURATION_SECONDS),
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(message, Severity::Info)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(message, Severity::Warning)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(message, Severity::Error)
+    }
+
+    #[allow(dead_code)]
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ToastQueue(VecDeque<Notification>);
+
+#[derive(Component)]
+struct ToastRoot;
+
+#[derive(Component)]
+struct Toast(Timer);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let r = root(toast_root, &asset_server, &mut commands, |_| {});
+    commands.entity(r).insert(ToastRoot);
+}
+
+fn queue_notifications(mut events: EventReader<Notification>, mut queue: ResMut<ToastQueue>) {
+    for event in events.read() {
+        queue.0.push_back(event.clone());
+    }
+}
+
+fn show_toasts(
+    mut commands: Commands,
+    mut queue: ResMut<ToastQueue>,
+    asset_server: Res<AssetServer>,
+    root: Query<Entity, With<ToastRoot>>,
+    toasts: Query<(), With<Toast>>,
+) {
+    let Ok(root) = root.get_single() else {
+        return;
+    };
+    let mut active = toasts.iter().count();
+    while active < MAX_TOASTS {
+        let Some(notification) = queue.0.pop_front() else {
+            return;
+        };
+        active += 1;
+
+        let mut bundle = NodeBundle::default();
+        toast(&mut bundle);
+        match notification.severity {
+            Severity::Info => {}
+            Severity::Warning => toast_warning(&mut bundle),
+            Severity::Error => toast_error(&mut bundle),
+        }
+        let mut style = TextStyle::default();
+        toast_text(&asset_server, &mut style);
+
+        commands.entity(root).with_children(|p| {
+            p.spawn((
+                bundle,
+                Toast(Timer::new(notification.duration, TimerMode::Once)),
+            ))
+            .with_children(|p| {
+                p.spawn(TextBundle::from_section(notification.message, style));
+            });
+        });
+    }
+}
+
+fn update_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toasts: Query<(
+        Entity,
+        &mut Toast,
+        &mut BackgroundColor,
+        &mut BorderColor,
+        &Children,
+    )>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, mut toast, mut background, mut border, children) in toasts.iter_mut() {
+        toast.0.tick(time.delta());
+        if toast.0.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let remaining = toast.0.remaining_secs();
+        if remaining > FADE_OUT_SECONDS {
+            continue;
+        }
+        let alpha = remaining / FADE_OUT_SECONDS;
+        background.0.set_a(alpha);
+        border.0.set_a(alpha);
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                for section in text.sections.iter_mut() {
+                    section.style.color.set_a(alpha);
+                }
+            }
+        }
+    }
+}